@@ -1,7 +1,32 @@
 use super::runner::environment::Environment;
+use super::runner::generator::Generator;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
 use std::rc::Rc;
 
+/// The names of the builtin types, as tested by `is` patterns and used in
+/// type annotations.
+pub const TYPE_NAMES: [&str; 14] = [
+    "int",
+    "float",
+    "number",
+    "string",
+    "bool",
+    "nil",
+    "function",
+    "class",
+    "instance",
+    "list",
+    "map",
+    "module",
+    "generator",
+    "range",
+];
+
 #[derive(Debug)]
 pub struct Program {
     pub statements: Vec<Statement>,
@@ -12,24 +37,112 @@ pub enum Statement {
     Expression(Expression),
     Print(Expression),
     Declaration(Declaration),
+    Destructuring(Destructuring),
+    Assignments(Assignments),
     Function(Function),
+    Class(Class),
     Block(Block),
     Conditional(Conditional),
     Iteration(Iteration),
-    Return(Expression),
+    ForIn(ForIn),
+    Match(Match),
+    Try(Try),
+    Throw(Expression),
+    Import(Import),
+    Export(Box<Statement>),
+    Return(Option<Expression>, Position),
+    Yield(Option<Expression>, Position),
+    Break(Position),
+    Continue(Position),
+}
+
+/// Where a node starts in the source, as the line and the UTF-16 column
+/// reported in diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Declaration {
     pub name: Rc<String>,
     pub value: Option<Expression>,
+    pub constant: bool,
+    pub annotation: Option<Type>,
+    pub doc: Option<String>,
+    pub position: Position,
+}
+
+/// `var (a, b) = value;`, declaring every name of the target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Destructuring {
+    pub target: Target,
+    pub value: Expression,
+    pub constant: bool,
+    pub position: Position,
+}
+
+/// The names a destructuring declaration unpacks a value into.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    Name(Rc<String>),
+    /// `(a, b)` or `[a, b, ...rest]`, unpacking the items of a list. Items
+    /// can be unpacked further by nested targets.
+    List(Vec<Target>, Option<Rc<String>>),
+    /// `{a, b}`, unpacking fields of an instance or entries of a map.
+    Fields(Vec<Rc<String>>),
+}
+
+/// `a, b = b, a;`, which evaluates every value before assigning any of the
+/// targets. A single value is unpacked from a list instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignments {
+    pub targets: Vec<Expression>,
+    pub values: Vec<Expression>,
+    /// In strict mode, the annotation that the item unpacked for each target
+    /// is checked against, along with the context reported when it doesn't
+    /// match.
+    pub checks: Vec<Option<(Type, String)>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Function {
     pub name: Rc<String>,
-    pub params: Vec<Rc<String>>,
+    pub params: Vec<Parameter>,
+    pub rest: Option<Rc<String>>,
     pub body: Box<Statement>,
+    pub doc: Option<String>,
+    /// Set by the parser when the body contains a `yield`. Calling such a
+    /// function returns a generator instead of running the body.
+    pub generator: bool,
+    pub returns: Option<Type>,
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: Rc<String>,
+    pub default: Option<Expression>,
+    pub constant: bool,
+    pub annotation: Option<Type>,
+}
+
+/// A type annotation such as `number`, or `string?` which also allows nil.
+/// The name is either a builtin type, `any`, or the name of a class.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Type {
+    pub name: Rc<String>,
+    pub nullable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Class {
+    pub name: Rc<String>,
+    pub superclass: Option<Expression>,
+    pub methods: Vec<Rc<Function>>,
+    pub doc: Option<String>,
+    pub position: Position,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -48,6 +161,70 @@ pub struct Conditional {
 pub struct Iteration {
     pub cond: Expression,
     pub body: Box<Statement>,
+    pub increment: Option<Expression>,
+}
+
+/// `for (var name in iterable) body`, which declares a fresh `name` for every
+/// item of the iterable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForIn {
+    pub name: Rc<String>,
+    pub iterable: Expression,
+    pub body: Box<Statement>,
+    /// The position of the iterable.
+    pub position: Position,
+}
+
+/// `import "path" as name;` or `import { a, b } from "path";`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    pub path: String,
+    pub names: ImportNames,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportNames {
+    Module(Rc<String>),
+    Exports(Vec<Rc<String>>),
+}
+
+/// A `try` statement. At least one of `catch` and `finally` is present.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Try {
+    pub body: Box<Statement>,
+    pub catch: Option<Catch>,
+    pub finally: Option<Box<Statement>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Catch {
+    pub name: Rc<String>,
+    pub body: Box<Statement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub value: Expression,
+    pub arms: Vec<Arm>,
+}
+
+/// One arm of a `match`. The arm is taken if any of its `patterns` matches
+/// and the `guard`, if there is one, is truthy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arm {
+    pub patterns: Vec<Pattern>,
+    pub guard: Option<Expression>,
+    pub body: Box<Statement>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Literal(Primary),
+    Binding(Rc<String>),
+    Wildcard,
+    /// A number in `start..end`, including `start` but not `end`.
+    Range(Primary, Primary),
+    Type(Rc<String>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -56,19 +233,43 @@ pub enum Expression {
     Unary(Unary),
     Binary(Binary),
     Logical(Logical),
+    Ternary(Ternary),
     Grouping(Box<Expression>),
     Assignment(Assignment),
+    Update(Update),
     Call(Call),
+    Lambda(Rc<Function>),
+    List(Vec<Expression>),
+    Map(Vec<(Expression, Expression)>),
+    Interpolation(Vec<Expression>),
+    Get(Get),
+    Set(Set),
+    Index(Index),
+    SetIndex(SetIndex),
+    Super(Super),
+    Check(Check),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
-    Variable(Rc<String>),
+    Variable(Variable),
+    This(Variable),
     Primary(Primary),
 }
 
+/// A reference to a named binding. `depth` is filled in by the resolver with
+/// the number of scopes between the reference and its declaration, and is
+/// left as `None` for globals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variable {
+    pub name: Rc<String>,
+    pub depth: Option<usize>,
+    pub position: Position,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Primary {
+    Integer(i64),
     Number(f64),
     String(String),
     Boolean(bool),
@@ -79,6 +280,8 @@ pub enum Primary {
 pub struct Unary {
     pub op: UnaryOp,
     pub expression: Box<Expression>,
+    /// The position of the operator.
+    pub position: Position,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -86,6 +289,8 @@ pub struct Binary {
     pub op: BinaryOp,
     pub left: Box<Expression>,
     pub right: Box<Expression>,
+    /// The position of the operator.
+    pub position: Position,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -95,16 +300,35 @@ pub struct Logical {
     pub right: Box<Expression>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ternary {
+    pub cond: Box<Expression>,
+    pub success: Box<Expression>,
+    pub failure: Box<Expression>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Assignment {
-    pub variable: Rc<String>,
+    pub variable: Variable,
+    pub value: Box<Expression>,
+}
+
+/// A compound assignment such as `x += 2`, or an increment or decrement,
+/// which is stored as `+= 1` or `-= 1`. `postfix` is set for `x++` and
+/// `x--`, which evaluate to the value before the update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update {
+    pub target: Box<Expression>,
+    pub op: BinaryOp,
     pub value: Box<Expression>,
+    pub postfix: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum UnaryOp {
     Not,
     Minus,
+    BitNot,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -119,6 +343,14 @@ pub enum BinaryOp {
     Minus,
     Multiply,
     Divide,
+    IntegerDivide,
+    Modulo,
+    Power,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -131,12 +363,110 @@ pub enum LogicalOp {
 pub struct Call {
     pub callee: Box<Expression>,
     pub arguments: Vec<Expression>,
+    /// The position of the opening parenthesis.
+    pub position: Position,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Get {
+    pub object: Box<Expression>,
+    pub name: Rc<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Set {
+    pub object: Box<Expression>,
+    pub name: Rc<String>,
+    pub value: Box<Expression>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Index {
+    pub object: Box<Expression>,
+    pub index: Box<Expression>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetIndex {
+    pub object: Box<Expression>,
+    pub index: Box<Expression>,
+    pub value: Box<Expression>,
+}
+
+/// A runtime type check inserted in `--strict-types` mode, which fails
+/// unless the value matches the annotation. `context` describes the value
+/// being checked for the error message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub value: Box<Expression>,
+    pub annotation: Type,
+    pub context: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Super {
+    pub variable: Variable,
+    pub method: Rc<String>,
 }
 
 #[derive(Debug, Clone)]
 pub enum Value {
     Literal(Primary),
-    Function(Function, Environment),
+    Function(Rc<Function>, Environment),
+    Native(Native),
+    Class(Rc<Class>, Environment),
+    Instance(Rc<Instance>),
+    List(Rc<RefCell<Vec<Rc<Value>>>>),
+    Map(Rc<RefCell<HashMap<Key, Rc<Value>>>>),
+    Module(Rc<Module>),
+    Generator(Rc<Generator>),
+    Range(Range),
+    /// A builtin method bound to the value it was looked up on, such as
+    /// `next` of a generator.
+    Method(Rc<Value>, &'static str),
+}
+
+/// A primitive value used as a map key. Keys are built with `Key::new`, which
+/// rejects NaN and folds `-0.0` into `0.0`, so that every key is equal to
+/// itself and equal numbers hash alike.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Key(Primary);
+
+pub type NativeFn = fn(Vec<Rc<Value>>) -> Result<Rc<Value>, String>;
+
+/// A function implemented in Rust and exposed to Lox code. `arity` holds the
+/// minimum and maximum number of arguments.
+#[derive(Debug, Clone)]
+pub struct Native {
+    pub name: &'static str,
+    pub arity: (usize, usize),
+    pub function: NativeFn,
+}
+
+/// The integers from `start` up to but excluding `end`, counting by `step`,
+/// as created by `range()`. A negative step counts down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Range {
+    pub start: i64,
+    pub end: i64,
+    pub step: i64,
+}
+
+/// A loaded module. Its exported names are looked up in the global scope of
+/// `env`, where the module was run.
+#[derive(Debug)]
+pub struct Module {
+    pub path: PathBuf,
+    pub env: Environment,
+    pub exports: Vec<Rc<String>>,
+}
+
+#[derive(Debug)]
+pub struct Instance {
+    pub class: Rc<Class>,
+    /// The environment the class was declared in, which holds its superclass.
+    pub class_env: Environment,
+    pub fields: RefCell<HashMap<Rc<String>, Rc<Value>>>,
 }
 
 impl PartialEq for Value {
@@ -144,8 +474,19 @@ impl PartialEq for Value {
         use self::Value::*;
 
         match (self, other) {
+            (Literal(Primary::Integer(s)), Literal(Primary::Number(o)))
+            | (Literal(Primary::Number(o)), Literal(Primary::Integer(s))) => *s as f64 == *o,
             (Literal(s), Literal(o)) => s == o,
-            (Function(s, _), Function(o, _)) => s == o,
+            (Function(s, s_env), Function(o, o_env)) => Rc::ptr_eq(s, o) && s_env.ptr_eq(o_env),
+            (Native(s), Native(o)) => s.name == o.name,
+            (Class(s, _), Class(o, _)) => Rc::ptr_eq(s, o),
+            (Instance(s), Instance(o)) => Rc::ptr_eq(s, o),
+            (List(s), List(o)) => Rc::ptr_eq(s, o),
+            (Map(s), Map(o)) => Rc::ptr_eq(s, o),
+            (Module(s), Module(o)) => Rc::ptr_eq(s, o),
+            (Generator(s), Generator(o)) => Rc::ptr_eq(s, o),
+            (Range(s), Range(o)) => s == o,
+            (Method(s, n), Method(o, m)) => s == o && n == m,
             _ => false,
         }
     }
@@ -153,9 +494,62 @@ impl PartialEq for Value {
 
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.format(f, &mut HashSet::new())
+    }
+}
+
+impl Value {
+    /// Formats the value, keeping the lists and maps being formatted in
+    /// `seen` so that one containing itself is printed as `[...]` or `{...}`
+    /// when reached again.
+    fn format(&self, f: &mut fmt::Formatter, seen: &mut HashSet<*const ()>) -> fmt::Result {
         match self {
             Value::Literal(literal) => write!(f, "{}", literal),
             Value::Function(func, _) => write!(f, "<function {}>", func.name),
+            Value::Native(native) => write!(f, "<native function {}>", native.name),
+            Value::Class(class, _) => write!(f, "<class {}>", class.name),
+            Value::Instance(instance) => write!(f, "<{} instance>", instance.class.name),
+            Value::List(list) => {
+                let pointer = Rc::as_ptr(list) as *const ();
+                if !seen.insert(pointer) {
+                    return write!(f, "[...]");
+                }
+                write!(f, "[")?;
+                for (i, item) in list.borrow().iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    item.format(f, seen)?;
+                }
+                seen.remove(&pointer);
+                write!(f, "]")
+            }
+            Value::Map(map) => {
+                let pointer = Rc::as_ptr(map) as *const ();
+                if !seen.insert(pointer) {
+                    return write!(f, "{{...}}");
+                }
+                let map = map.borrow();
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: ", key.0)?;
+                    value.format(f, seen)?;
+                }
+                seen.remove(&pointer);
+                write!(f, "}}")
+            }
+            Value::Module(module) => write!(f, "<module {}>", module.path.display()),
+            Value::Generator(generator) => write!(f, "<generator {}>", generator.name()),
+            Value::Range(range) => {
+                write!(f, "range({}, {}, {})", range.start, range.end, range.step)
+            }
+            Value::Method(_, name) => write!(f, "<native method {}>", name),
         }
     }
 }
@@ -163,7 +557,8 @@ impl fmt::Display for Value {
 impl fmt::Display for Primary {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Primary::Number(number) => write!(f, "{}", number),
+            Primary::Integer(integer) => write!(f, "{}", integer),
+            Primary::Number(number) => write!(f, "{:?}", number),
             Primary::String(string) => write!(f, "{}", string),
             Primary::Boolean(boolean) => write!(f, "{}", boolean),
             Primary::Nil => write!(f, "nil"),
@@ -171,14 +566,287 @@ impl fmt::Display for Primary {
     }
 }
 
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "L{}:{}", self.line, self.column)
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.nullable {
+            write!(f, "?")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            BinaryOp::EqualEqual => "==",
+            BinaryOp::NotEqual => "!=",
+            BinaryOp::Less => "<",
+            BinaryOp::LessEqual => "<=",
+            BinaryOp::Greater => ">",
+            BinaryOp::GreaterEqual => ">=",
+            BinaryOp::Plus => "+",
+            BinaryOp::Minus => "-",
+            BinaryOp::Multiply => "*",
+            BinaryOp::Divide => "/",
+            BinaryOp::IntegerDivide => "~/",
+            BinaryOp::Modulo => "%",
+            BinaryOp::Power => "**",
+            BinaryOp::BitAnd => "&",
+            BinaryOp::BitOr => "|",
+            BinaryOp::BitXor => "^",
+            BinaryOp::ShiftLeft => "<<",
+            BinaryOp::ShiftRight => ">>",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+impl Key {
+    pub fn new(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Literal(Primary::Number(number)) if number.is_nan() => {
+                Err("NaN cannot be used as a map key".to_string())
+            }
+            Value::Literal(Primary::Number(number))
+                if number.fract() == 0.0 && number.abs() < i64::MAX as f64 =>
+            {
+                Ok(Key(Primary::Integer(*number as i64)))
+            }
+            Value::Literal(primary @ Primary::Integer(_))
+            | Value::Literal(primary @ Primary::Number(_))
+            | Value::Literal(primary @ Primary::String(_))
+            | Value::Literal(primary @ Primary::Boolean(_)) => Ok(Key(primary.clone())),
+            value => Err(format!("{} cannot be used as a map key", value)),
+        }
+    }
+
+    pub fn value(&self) -> Value {
+        Value::Literal(self.0.clone())
+    }
+
+    fn rank(&self) -> u8 {
+        match self.0 {
+            Primary::Boolean(_) => 0,
+            Primary::Integer(_) | Primary::Number(_) => 1,
+            Primary::String(_) => 2,
+            Primary::Nil => 3,
+        }
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        match &self.0 {
+            Primary::Integer(integer) => integer.hash(state),
+            Primary::Number(number) => number.to_bits().hash(state),
+            Primary::String(string) => string.hash(state),
+            Primary::Boolean(boolean) => boolean.hash(state),
+            Primary::Nil => (),
+        }
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Key) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Key) -> Ordering {
+        match (&self.0, &other.0) {
+            (Primary::Integer(s), Primary::Integer(o)) => s.cmp(o),
+            (Primary::Integer(s), Primary::Number(o)) => (*s as f64).total_cmp(o),
+            (Primary::Number(s), Primary::Integer(o)) => s.total_cmp(&(*o as f64)),
+            (Primary::Number(s), Primary::Number(o)) => s.total_cmp(o),
+            (Primary::String(s), Primary::String(o)) => s.cmp(o),
+            (Primary::Boolean(s), Primary::Boolean(o)) => s.cmp(o),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl Module {
+    /// Looks up an exported name.
+    pub fn get(&self, name: &Rc<String>) -> Option<Rc<Value>> {
+        if self.exports.contains(name) {
+            self.env.get_at(None, name)
+        } else {
+            None
+        }
+    }
+}
+
+impl Iterator for Range {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let remaining = if self.step > 0 {
+            self.start < self.end
+        } else {
+            self.start > self.end
+        };
+        if !remaining {
+            return None;
+        }
+
+        let current = self.start;
+        // once stepping past `i64::MAX` would overflow, no items are left
+        self.start = current.checked_add(self.step).unwrap_or(self.end);
+        Some(current)
+    }
+}
+
+impl Target {
+    /// The names declared by the target, in order.
+    pub fn names(&self) -> Vec<&Rc<String>> {
+        match self {
+            Target::Name(name) => vec![name],
+            Target::List(items, rest) => items
+                .iter()
+                .flat_map(Target::names)
+                .chain(rest.iter())
+                .collect(),
+            Target::Fields(names) => names.iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Target::Name(name) => write!(f, "{}", name),
+            Target::List(items, rest) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                if let Some(rest) = rest {
+                    if !items.is_empty() {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "...{}", rest)?;
+                }
+                write!(f, "]")
+            }
+            Target::Fields(names) => {
+                let names: Vec<_> = names.iter().map(|name| name.as_str()).collect();
+                write!(f, "{{{}}}", names.join(", "))
+            }
+        }
+    }
+}
+
+impl Variable {
+    pub fn new(name: Rc<String>, position: Position) -> Self {
+        Variable {
+            name,
+            depth: None,
+            position,
+        }
+    }
+}
+
+impl Statement {
+    /// Whether a function body contains a `yield`, outside of nested
+    /// functions.
+    pub fn yields(&self) -> bool {
+        match self {
+            Statement::Yield(..) => true,
+            Statement::Block(block) => block.statements.iter().any(Statement::yields),
+            Statement::Conditional(conditional) => {
+                conditional.success.yields()
+                    || conditional
+                        .failure
+                        .as_deref()
+                        .is_some_and(Statement::yields)
+            }
+            Statement::Iteration(iteration) => iteration.body.yields(),
+            Statement::ForIn(for_in) => for_in.body.yields(),
+            Statement::Match(matching) => matching.arms.iter().any(|arm| arm.body.yields()),
+            Statement::Try(try_statement) => {
+                try_statement.body.yields()
+                    || try_statement
+                        .catch
+                        .as_ref()
+                        .is_some_and(|catch| catch.body.yields())
+                    || try_statement
+                        .finally
+                        .as_deref()
+                        .is_some_and(Statement::yields)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Function {
+    /// The minimum number of arguments, and the maximum unless the function
+    /// takes a rest parameter.
+    pub fn arity(&self) -> (usize, Option<usize>) {
+        let required = self
+            .params
+            .iter()
+            .take_while(|param| param.default.is_none())
+            .count();
+        let max = match self.rest {
+            Some(_) => None,
+            None => Some(self.params.len()),
+        };
+
+        (required, max)
+    }
+}
+
+impl Class {
+    pub fn find_method(&self, name: &str) -> Option<&Rc<Function>> {
+        self.methods
+            .iter()
+            .find(|method| method.name.as_str() == name)
+    }
+}
+
 impl Value {
-    pub fn is_truthy(&self) -> bool {
+    pub fn list(items: Vec<Rc<Value>>) -> Self {
+        Value::List(Rc::new(RefCell::new(items)))
+    }
+
+    /// The name of the value's type, as used by `is` patterns.
+    pub fn type_name(&self) -> &'static str {
         match self {
-            Value::Literal(literal) => match literal {
-                Primary::Boolean(false) | Primary::Nil => false,
-                _ => true,
-            },
-            _ => true,
+            Value::Literal(Primary::Integer(_)) => "int",
+            Value::Literal(Primary::Number(_)) => "float",
+            Value::Literal(Primary::String(_)) => "string",
+            Value::Literal(Primary::Boolean(_)) => "bool",
+            Value::Literal(Primary::Nil) => "nil",
+            Value::Function(..) | Value::Native(_) | Value::Method(..) => "function",
+            Value::Class(..) => "class",
+            Value::Instance(_) => "instance",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+            Value::Module(_) => "module",
+            Value::Generator(_) => "generator",
+            Value::Range(_) => "range",
         }
     }
+
+    pub fn is_truthy(&self) -> bool {
+        !matches!(
+            self,
+            Value::Literal(Primary::Boolean(false)) | Value::Literal(Primary::Nil)
+        )
+    }
 }
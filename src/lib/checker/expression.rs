@@ -0,0 +1,364 @@
+use super::{arithmetic, is_nullable, is_number, join, named, non_nil, Checker, Inferable, Symbol};
+use crate::ast::Expression::*;
+use crate::ast::{
+    Assignment, Binary, BinaryOp, Call, Expression, Get, Index, Literal, Logical, LogicalOp,
+    Position, Primary, Set, SetIndex, Ternary, Type, Unary, UnaryOp, Update,
+};
+use std::rc::Rc;
+
+impl Inferable for Expression {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        match self {
+            Literal(literal) => literal.infer(checker),
+            Unary(unary) => unary.infer(checker),
+            Binary(binary) => binary.infer(checker),
+            Logical(logical) => logical.infer(checker),
+            Ternary(ternary) => ternary.infer(checker),
+            Grouping(expression) => expression.infer(checker),
+            Assignment(assignment) => assignment.infer(checker),
+            Update(update) => {
+                let found = update.infer(checker);
+                if let Some(assignment) = enforce_update(checker, update) {
+                    *self = assignment;
+                }
+                found
+            }
+            Call(call) => call.infer(checker),
+            Lambda(function) => {
+                checker.check_function(Rc::make_mut(function));
+                named("function")
+            }
+            List(items) => {
+                for item in items {
+                    item.infer(checker);
+                }
+                named("list")
+            }
+            Interpolation(parts) => {
+                for part in parts {
+                    part.infer(checker);
+                }
+                named("string")
+            }
+            Map(entries) => {
+                for (key, value) in entries {
+                    key.infer(checker);
+                    value.infer(checker);
+                }
+                named("map")
+            }
+            Get(get) => get.infer(checker),
+            Set(set) => set.infer(checker),
+            Index(index) => index.infer(checker),
+            SetIndex(set_index) => set_index.infer(checker),
+            Super(_) => named("any"),
+            Check(check) => {
+                check.value.infer(checker);
+                check.annotation.clone()
+            }
+        }
+    }
+}
+
+impl Inferable for Literal {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        match self {
+            Literal::Variable(var) => {
+                checker.position = var.position;
+                checker.variable_type(&var.name)
+            }
+            Literal::This(_) => named("any"),
+            Literal::Primary(primary) => named(match primary {
+                Primary::Integer(_) => "int",
+                Primary::Number(_) => "float",
+                Primary::String(_) => "string",
+                Primary::Boolean(_) => "bool",
+                Primary::Nil => "nil",
+            }),
+        }
+    }
+}
+
+impl Inferable for Unary {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        let found = self.expression.infer(checker);
+        let op = match self.op {
+            UnaryOp::Not => return named("bool"),
+            UnaryOp::Minus => "-",
+            UnaryOp::BitNot => "~",
+        };
+
+        if is_nullable(&found) {
+            checker.check_nil(&self.expression, &found, &format!("apply '{}' to", op));
+            return named("any");
+        }
+        match (&self.op, found.name.as_str()) {
+            (_, "any") => named("any"),
+            (UnaryOp::BitNot, _) if is_number(&found) => named("int"),
+            _ if is_number(&found) => found,
+            _ => {
+                let message = format!("'{}' operator is not defined for {}", op, found);
+                checker.error(self.position, message);
+                named("any")
+            }
+        }
+    }
+}
+
+impl Inferable for Binary {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        let left = self.left.infer(checker);
+        let right = self.right.infer(checker);
+        binary(
+            checker,
+            &self.op,
+            self.position,
+            (&self.left, left),
+            (&self.right, right),
+        )
+    }
+}
+
+/// Checks the operands of a binary operator other than a logical one, and
+/// infers the type of its result. Errors are reported at `position`.
+fn binary(
+    checker: &mut Checker,
+    op: &BinaryOp,
+    position: Position,
+    (left_value, left): (&Expression, Type),
+    (right_value, right): (&Expression, Type),
+) -> Type {
+    use crate::ast::BinaryOp::*;
+
+    if let EqualEqual | NotEqual = op {
+        return named("bool");
+    }
+    if is_nullable(&left) || is_nullable(&right) {
+        let usage = format!("apply '{}' to", op);
+        checker.check_nil(left_value, &left, &usage);
+        checker.check_nil(right_value, &right, &usage);
+        return named("any");
+    }
+
+    let comparison = matches!(op, Less | LessEqual | Greater | GreaterEqual);
+    let strings = *op == Plus && left.name.as_str() == "string" && right.name.as_str() == "string";
+    let known = |found: &Type| found.name.as_str() != "any";
+    let invalid = |found: &Type| {
+        known(found) && !is_number(found) && !(*op == Plus && found.name.as_str() == "string")
+    };
+
+    if strings {
+        named("string")
+    } else if is_number(&left) && is_number(&right) {
+        arithmetic(op, &left, &right)
+    } else if (known(&left) && known(&right)) || invalid(&left) || invalid(&right) {
+        let message = format!(
+            "'{}' operator is not defined for {} and {}",
+            op, left, right
+        );
+        checker.error(position, message);
+        named("any")
+    } else if comparison {
+        named("bool")
+    } else {
+        named("any")
+    }
+}
+
+impl Inferable for Logical {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        let left = self.left.infer(checker);
+
+        let truthy = self.op == LogicalOp::And;
+        checker.begin_scope();
+        checker.narrow(non_nil(&self.left, truthy));
+        let right = self.right.infer(checker);
+        checker.end_scope();
+
+        match self.op {
+            // the left value is only the result when it's truthy
+            LogicalOp::Or => join(
+                Type {
+                    nullable: false,
+                    ..left
+                },
+                right,
+            ),
+            LogicalOp::And => join(left, right),
+        }
+    }
+}
+
+impl Inferable for Ternary {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        self.cond.infer(checker);
+
+        checker.begin_scope();
+        checker.narrow(non_nil(&self.cond, true));
+        let success = self.success.infer(checker);
+        checker.end_scope();
+
+        checker.begin_scope();
+        checker.narrow(non_nil(&self.cond, false));
+        let failure = self.failure.infer(checker);
+        checker.end_scope();
+
+        join(success, failure)
+    }
+}
+
+impl Inferable for Assignment {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        let name = self.variable.name.clone();
+        let found = match checker.annotation(&name) {
+            Some(annotation) => {
+                let context = format!("Variable '{}'", name);
+                let position = self.variable.position;
+                let found = checker.expect(&annotation, &mut self.value, &context, position);
+                checker.enforce(&mut self.value, &annotation, &context);
+                found
+            }
+            _ => self.value.infer(checker),
+        };
+        checker.assigned(&name, !is_nullable(&found));
+
+        found
+    }
+}
+
+impl Inferable for Update {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        let target = self.target.infer(checker);
+        let value = self.value.infer(checker);
+        let position = checker.locate(&self.target);
+        let found = binary(
+            checker,
+            &self.op,
+            position,
+            (&self.target, target),
+            (&self.value, value),
+        );
+
+        if let Expression::Literal(Literal::Variable(variable)) = &*self.target {
+            if let Some(annotation) = checker.annotation(&variable.name) {
+                if !checker.accepts(&annotation, &found) {
+                    let message = format!(
+                        "Variable '{}' expects {}, found {}",
+                        variable.name, annotation, found
+                    );
+                    checker.error(variable.position, message);
+                }
+            }
+        }
+
+        found
+    }
+}
+
+/// In strict mode, rewrites a compound assignment to an annotated variable,
+/// such as `x += y`, as `x = x + y` with the new value checked against the
+/// annotation. `x++` and `x--` are left as they are, since adding 1 to a
+/// number of an annotated type keeps its type.
+fn enforce_update(checker: &Checker, update: &mut Update) -> Option<Expression> {
+    let variable = match (&*update.target, update.postfix) {
+        (Expression::Literal(Literal::Variable(variable)), false) if checker.strict => {
+            variable.clone()
+        }
+        _ => return None,
+    };
+    let annotation = checker.annotation(&variable.name)?;
+
+    let mut value = Expression::Binary(Binary {
+        op: update.op.clone(),
+        left: update.target.clone(),
+        right: update.value.clone(),
+        position: variable.position,
+    });
+    checker.enforce(
+        &mut value,
+        &annotation,
+        &format!("Variable '{}'", variable.name),
+    );
+    Some(Expression::Assignment(Assignment {
+        variable,
+        value: Box::new(value),
+    }))
+}
+
+impl Inferable for Call {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        let symbol = match &*self.callee {
+            Expression::Literal(Literal::Variable(var)) => checker.lookup(&var.name).cloned(),
+            _ => None,
+        };
+        let callee = self.callee.infer(checker);
+        checker.check_nil(&self.callee, &callee, "call");
+        checker.position = self.position;
+
+        match symbol {
+            Some(Symbol::Function(signature)) => {
+                let mut params = signature.params.iter();
+                for argument in &mut self.arguments {
+                    match params.next() {
+                        Some((name, Some(annotation))) => {
+                            let context = format!("Parameter '{}' of {}", name, signature.name);
+                            checker.expect(annotation, argument, &context, self.position);
+                        }
+                        _ => {
+                            argument.infer(checker);
+                        }
+                    }
+                }
+                signature.result.clone()
+            }
+            symbol => {
+                for argument in &mut self.arguments {
+                    argument.infer(checker);
+                }
+                match (symbol, &*self.callee) {
+                    (Some(Symbol::Class(_)), Expression::Literal(Literal::Variable(var))) => Type {
+                        name: var.name.clone(),
+                        nullable: false,
+                    },
+                    _ => named("any"),
+                }
+            }
+        }
+    }
+}
+
+impl Inferable for Get {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        let object = self.object.infer(checker);
+        let usage = format!("read property '{}' of", self.name);
+        checker.check_nil(&self.object, &object, &usage);
+        named("any")
+    }
+}
+
+impl Inferable for Set {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        let object = self.object.infer(checker);
+        let usage = format!("set property '{}' of", self.name);
+        checker.check_nil(&self.object, &object, &usage);
+        self.value.infer(checker)
+    }
+}
+
+impl Inferable for Index {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        let object = self.object.infer(checker);
+        checker.check_nil(&self.object, &object, "index");
+        self.index.infer(checker);
+        named("any")
+    }
+}
+
+impl Inferable for SetIndex {
+    fn infer(&mut self, checker: &mut Checker) -> Type {
+        let object = self.object.infer(checker);
+        checker.check_nil(&self.object, &object, "index");
+        self.index.infer(checker);
+        self.value.infer(checker)
+    }
+}
@@ -0,0 +1,594 @@
+mod expression;
+mod statement;
+
+use crate::ast::{
+    BinaryOp, Check, Expression, Function, ImportNames, Literal, LogicalOp, Position, Primary,
+    Program, Statement, Type, UnaryOp, Variable, TYPE_NAMES,
+};
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::rc::Rc;
+
+/// What the checker knows about a name.
+#[derive(Clone)]
+enum Symbol {
+    /// A variable, with its annotation if it has one. `non_nil` is set where
+    /// the variable is known not to hold nil, such as in the body of
+    /// `if (x != nil)`.
+    Variable {
+        annotation: Option<Type>,
+        non_nil: bool,
+    },
+    /// An annotated variable in a scope where it is known not to hold nil,
+    /// such as the body of `if (x != nil)`, shadowing its declaration.
+    Narrowed(Type),
+    Function(Rc<Signature>),
+    /// A class, with the name of its superclass.
+    Class(Option<Rc<String>>),
+}
+
+/// The annotated parameters of a function declared with `fun`, and the type
+/// of the value a call returns.
+struct Signature {
+    name: Rc<String>,
+    params: Vec<(Rc<String>, Option<Type>)>,
+    result: Type,
+}
+
+/// The function whose body is being checked.
+struct Enclosing {
+    name: Rc<String>,
+    returns: Option<Type>,
+    generator: bool,
+}
+
+/// Walks the syntax tree before it is resolved, reporting values that don't
+/// match their type annotations and uses of values that are possibly nil.
+/// Code without annotations is left unchecked. In strict mode, the checks
+/// are also inserted into the tree so that annotations hold while it runs.
+struct Checker {
+    scopes: Vec<HashMap<Rc<String>, Symbol>>,
+    function: Option<Enclosing>,
+    /// The names of the classes declared or possibly imported anywhere in
+    /// the program, which can be used as annotations.
+    classes: HashSet<Rc<String>>,
+    /// Every annotation in the program with where it was written, checked
+    /// once all of the classes are known.
+    annotations: Vec<(Type, Position)>,
+    /// The position of the last node checked that has one, where errors
+    /// about nodes without a position of their own are reported.
+    position: Position,
+    strict: bool,
+    errors: Vec<String>,
+}
+
+trait Checkable {
+    fn check(&mut self, checker: &mut Checker);
+}
+
+/// Checks an expression and infers the type of its value, which is `any`
+/// when it isn't known.
+trait Inferable {
+    fn infer(&mut self, checker: &mut Checker) -> Type;
+}
+
+impl Program {
+    pub fn check(&mut self, strict: bool) -> Result<(), Vec<String>> {
+        let mut checker = Checker {
+            scopes: vec![HashMap::new()],
+            function: None,
+            classes: declared_classes(&self.statements),
+            annotations: vec![],
+            position: Position::default(),
+            strict,
+            errors: vec![],
+        };
+
+        checker.check_statements(&mut self.statements);
+        for (annotation, position) in mem::take(&mut checker.annotations) {
+            let name = annotation.name.as_str();
+            if name != "any"
+                && !TYPE_NAMES.contains(&name)
+                && !checker.classes.contains(&annotation.name)
+            {
+                checker.error(position, format!("Unknown type '{}'", name));
+            }
+        }
+
+        if !checker.errors.is_empty() {
+            Err(checker.errors)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Checker {
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, name: Rc<String>, symbol: Symbol) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, symbol);
+        }
+    }
+
+    /// Reports an error at `position`, in the format of parser errors.
+    fn error(&mut self, position: Position, message: String) {
+        self.errors.push(format!("{} {}", position, message));
+    }
+
+    /// Records an annotation, to report it if it doesn't name a type.
+    fn annotated(&mut self, annotation: &Option<Type>) {
+        if let Some(annotation) = annotation {
+            self.annotations.push((annotation.clone(), self.position));
+        }
+    }
+
+    fn lookup(&self, name: &Rc<String>) -> Option<&Symbol> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Checks the statements of a block. After `if (x == nil) return;`, `x`
+    /// is known not to be nil for the rest of the block.
+    fn check_statements(&mut self, statements: &mut [Statement]) {
+        for statement in statements {
+            statement.check(self);
+            if let Statement::Conditional(conditional) = statement {
+                if conditional.failure.is_none() && exits(&conditional.success) {
+                    self.narrow(non_nil(&conditional.cond, false));
+                }
+            }
+        }
+    }
+
+    /// The annotation of a variable, if it has one.
+    fn annotation(&self, name: &Rc<String>) -> Option<Type> {
+        match self.lookup(name) {
+            Some(Symbol::Variable { annotation, .. }) => annotation.clone(),
+            Some(Symbol::Narrowed(annotation)) => Some(annotation.clone()),
+            _ => None,
+        }
+    }
+
+    /// Marks annotated variables as holding a value in the innermost scope.
+    fn narrow(&mut self, names: Vec<Rc<String>>) {
+        for name in names {
+            if let Some(annotation) = self.annotation(&name) {
+                self.declare(name, Symbol::Narrowed(annotation));
+            }
+        }
+    }
+
+    /// Records an assignment to a variable. A value that is possibly nil
+    /// undoes any narrowing of the variable, while a value that isn't only
+    /// narrows it in the innermost scope, as the assignment may not happen
+    /// outside of it.
+    fn assigned(&mut self, name: &Rc<String>, non_nil: bool) {
+        let annotation = self.annotation(name);
+        let innermost = self.scopes.len() - 1;
+        let mut narrow = false;
+
+        for (depth, scope) in self.scopes.iter_mut().enumerate().rev() {
+            match scope.get(name) {
+                None => (),
+                Some(Symbol::Function(_)) | Some(Symbol::Class(_)) => {
+                    scope.insert(name.clone(), untyped());
+                    return;
+                }
+                Some(Symbol::Narrowed(_)) if non_nil => return,
+                Some(Symbol::Narrowed(_)) => {
+                    scope.remove(name);
+                }
+                Some(Symbol::Variable { .. }) if non_nil && depth < innermost => {
+                    narrow = true;
+                    break;
+                }
+                Some(Symbol::Variable { .. }) => {
+                    let symbol = Symbol::Variable {
+                        annotation,
+                        non_nil,
+                    };
+                    scope.insert(name.clone(), symbol);
+                    return;
+                }
+            }
+        }
+
+        if let (true, Some(annotation)) = (narrow, annotation) {
+            self.declare(name.clone(), Symbol::Narrowed(annotation));
+        }
+    }
+
+    fn variable_type(&self, name: &Rc<String>) -> Type {
+        match self.lookup(name) {
+            Some(Symbol::Variable {
+                annotation: Some(annotation),
+                non_nil,
+            }) => Type {
+                name: annotation.name.clone(),
+                nullable: annotation.nullable && !non_nil,
+            },
+            Some(Symbol::Narrowed(annotation)) => Type {
+                nullable: false,
+                ..annotation.clone()
+            },
+            Some(Symbol::Function(_)) => named("function"),
+            Some(Symbol::Class(_)) => named("class"),
+            _ => named("any"),
+        }
+    }
+
+    /// Checks and infers a value, reporting it unless it matches `expected`.
+    /// `context` describes what the value is for, as in runtime type errors,
+    /// and `position` is where the error is reported.
+    fn expect(
+        &mut self,
+        expected: &Type,
+        value: &mut Expression,
+        context: &str,
+        position: Position,
+    ) -> Type {
+        let found = value.infer(self);
+        if !self.accepts(expected, &found) {
+            let message = format!("{} expects {}, found {}", context, expected, found);
+            self.error(position, message);
+        }
+        found
+    }
+
+    /// Wraps a value in a runtime check of its type when in strict mode.
+    fn enforce(&self, value: &mut Expression, annotation: &Type, context: &str) {
+        if self.strict {
+            let unchecked =
+                mem::replace(value, Expression::Literal(Literal::Primary(Primary::Nil)));
+            *value = Expression::Check(Check {
+                value: Box::new(unchecked),
+                annotation: annotation.clone(),
+                context: context.to_string(),
+            });
+        }
+    }
+
+    /// Whether a value of type `found` can be used where `expected` is
+    /// annotated. Classes that aren't known are assumed to match, and so
+    /// are annotations that aren't types, which are reported on their own.
+    fn accepts(&self, expected: &Type, found: &Type) -> bool {
+        if expected.name.as_str() == "any" || found.name.as_str() == "any" {
+            return true;
+        }
+        if !TYPE_NAMES.contains(&expected.name.as_str()) && !self.classes.contains(&expected.name) {
+            return true;
+        }
+        if found.name.as_str() == "nil" {
+            return expected.nullable || expected.name.as_str() == "nil";
+        }
+        if found.nullable && !expected.nullable {
+            return false;
+        }
+
+        match (expected.name.as_str(), found.name.as_str()) {
+            (expected, found) if expected == found => true,
+            ("number", "int") | ("number", "float") => true,
+            ("instance", found) => !TYPE_NAMES.contains(&found),
+            (expected, found) if TYPE_NAMES.contains(&expected) || TYPE_NAMES.contains(&found) => {
+                false
+            }
+            (_, found) => self.is_subclass(&Rc::new(found.to_string()), &expected.name),
+        }
+    }
+
+    fn is_subclass(&self, class: &Rc<String>, superclass: &Rc<String>) -> bool {
+        if class == superclass {
+            return true;
+        }
+        match self.lookup(class) {
+            Some(Symbol::Class(Some(parent))) => self.is_subclass(parent, superclass),
+            Some(Symbol::Class(None)) => false,
+            _ => true,
+        }
+    }
+
+    /// Reports a use of a value that is possibly nil, such as calling it.
+    fn check_nil(&mut self, value: &Expression, found: &Type, usage: &str) {
+        if is_nullable(found) {
+            let message = format!(
+                "Cannot {} {}, which is possibly nil",
+                usage,
+                describe(value)
+            );
+            self.error(self.locate(value), message);
+        }
+    }
+
+    /// Where to report an error about an expression: its own position, or
+    /// that of the node it is part of.
+    fn locate(&self, expression: &Expression) -> Position {
+        match expression {
+            Expression::Literal(Literal::Variable(variable)) => variable.position,
+            Expression::Literal(Literal::This(variable)) => variable.position,
+            Expression::Call(call) => self.locate(&call.callee),
+            Expression::Lambda(function) => function.position,
+            Expression::Grouping(expression) => self.locate(expression),
+            Expression::Unary(unary) => unary.position,
+            Expression::Binary(binary) => binary.position,
+            Expression::Get(get) => self.locate(&get.object),
+            Expression::Index(index) => self.locate(&index.object),
+            _ => self.position,
+        }
+    }
+
+    fn check_function(&mut self, function: &mut Function) {
+        let generator = function.generator;
+        self.position = function.position;
+        self.annotated(&function.returns);
+        if let (Some(annotation), false) = (&function.returns, generator) {
+            if !self.accepts(annotation, &named("nil")) && !returns(&function.body) {
+                let message = format!(
+                    "Function '{}' returns {}, but can reach the end of its body without returning",
+                    function.name, annotation
+                );
+                self.error(function.position, message);
+            }
+        }
+        let enclosing = self.function.replace(Enclosing {
+            name: function.name.clone(),
+            returns: function.returns.clone(),
+            generator,
+        });
+
+        self.begin_scope();
+        let mut checks = vec![];
+        for param in &mut function.params {
+            self.annotated(&param.annotation);
+            let context = format!("Parameter '{}' of {}", param.name, function.name);
+            match (&param.annotation, &mut param.default) {
+                (Some(annotation), Some(default)) => {
+                    self.expect(annotation, default, &context, function.position);
+                }
+                (None, Some(default)) => {
+                    default.infer(self);
+                }
+                _ => (),
+            }
+            if let (true, Some(annotation)) = (self.strict, &param.annotation) {
+                checks.push(Statement::Expression(Expression::Check(Check {
+                    value: Box::new(variable(&param.name, function.position)),
+                    annotation: annotation.clone(),
+                    context,
+                })));
+            }
+            self.declare(
+                param.name.clone(),
+                Symbol::Variable {
+                    annotation: param.annotation.clone(),
+                    non_nil: false,
+                },
+            );
+        }
+        if let Some(rest) = &function.rest {
+            self.declare(
+                rest.clone(),
+                Symbol::Variable {
+                    annotation: Some(named("list")),
+                    non_nil: true,
+                },
+            );
+        }
+        function.body.check(self);
+        self.end_scope();
+
+        // in strict mode, parameters are checked on entry
+        if let (true, Statement::Block(body)) = (self.strict, &mut *function.body) {
+            checks.append(&mut body.statements);
+            body.statements = checks;
+        }
+
+        self.function = enclosing;
+    }
+}
+
+/// The names of the classes declared or imported at the top level of a
+/// program, which can be used as annotations before their declaration.
+fn declared_classes(statements: &[Statement]) -> HashSet<Rc<String>> {
+    let mut classes = HashSet::new();
+    for statement in statements {
+        match statement {
+            Statement::Class(class) => {
+                classes.insert(class.name.clone());
+            }
+            Statement::Export(declaration) => {
+                if let Statement::Class(class) = &**declaration {
+                    classes.insert(class.name.clone());
+                }
+            }
+            Statement::Import(import) => match &import.names {
+                ImportNames::Module(_) => (),
+                ImportNames::Exports(names) => classes.extend(names.iter().cloned()),
+            },
+            _ => (),
+        }
+    }
+    classes
+}
+
+/// The type named `name`, which doesn't include nil.
+fn named(name: &str) -> Type {
+    Type {
+        name: Rc::new(name.to_string()),
+        nullable: false,
+    }
+}
+
+fn is_nullable(found: &Type) -> bool {
+    found.name.as_str() != "any" && (found.nullable || found.name.as_str() == "nil")
+}
+
+fn is_number(found: &Type) -> bool {
+    matches!(found.name.as_str(), "int" | "float" | "number")
+}
+
+/// The type of a value that is either of two types.
+fn join(left: Type, right: Type) -> Type {
+    match (left.name.as_str(), right.name.as_str()) {
+        ("any", _) | (_, "any") => named("any"),
+        ("nil", _) => Type {
+            nullable: true,
+            ..right
+        },
+        (_, "nil") => Type {
+            nullable: true,
+            ..left
+        },
+        (l, r) if l == r => Type {
+            nullable: left.nullable || right.nullable,
+            ..left
+        },
+        _ if is_number(&left) && is_number(&right) => Type {
+            name: Rc::new("number".to_string()),
+            nullable: left.nullable || right.nullable,
+        },
+        _ => named("any"),
+    }
+}
+
+/// The type of applying an arithmetic or comparison operator to numbers.
+fn arithmetic(op: &BinaryOp, left: &Type, right: &Type) -> Type {
+    use crate::ast::BinaryOp::*;
+
+    let name = match (op, left.name.as_str(), right.name.as_str()) {
+        (Less | LessEqual | Greater | GreaterEqual, _, _) => "bool",
+        (Divide, _, _) => "float",
+        (BitAnd | BitOr | BitXor | ShiftLeft | ShiftRight, _, _) => "int",
+        (Power, _, _) => "number",
+        (_, "int", "int") => "int",
+        (_, "float", _) | (_, _, "float") => "float",
+        _ => "number",
+    };
+    named(name)
+}
+
+/// The names of the nullable variables known to hold a value when `cond`
+/// is `truthy`.
+fn non_nil(cond: &Expression, truthy: bool) -> Vec<Rc<String>> {
+    match cond {
+        Expression::Literal(Literal::Variable(variable)) if truthy => vec![variable.name.clone()],
+        Expression::Grouping(expression) => non_nil(expression, truthy),
+        Expression::Unary(unary) if unary.op == UnaryOp::Not => non_nil(&unary.expression, !truthy),
+        Expression::Binary(binary) => {
+            let compared = match (&*binary.left, &*binary.right) {
+                (Expression::Literal(Literal::Variable(variable)), nil)
+                | (nil, Expression::Literal(Literal::Variable(variable)))
+                    if *nil == Expression::Literal(Literal::Primary(Primary::Nil)) =>
+                {
+                    variable.name.clone()
+                }
+                _ => return vec![],
+            };
+            match (&binary.op, truthy) {
+                (BinaryOp::NotEqual, true) | (BinaryOp::EqualEqual, false) => vec![compared],
+                _ => vec![],
+            }
+        }
+        Expression::Logical(logical) => match (&logical.op, truthy) {
+            (LogicalOp::And, true) | (LogicalOp::Or, false) => {
+                let mut names = non_nil(&logical.left, truthy);
+                names.extend(non_nil(&logical.right, truthy));
+                names
+            }
+            _ => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+/// Whether running a statement never continues past it.
+fn exits(statement: &Statement) -> bool {
+    match statement {
+        Statement::Return(..)
+        | Statement::Throw(_)
+        | Statement::Break(_)
+        | Statement::Continue(_) => true,
+        Statement::Block(block) => block.statements.last().is_some_and(exits),
+        Statement::Conditional(conditional) => {
+            exits(&conditional.success) && conditional.failure.as_deref().is_some_and(exits)
+        }
+        _ => false,
+    }
+}
+
+/// Whether running a statement always ends in returning from the function
+/// or throwing. A `match` without a matching arm throws.
+fn returns(statement: &Statement) -> bool {
+    match statement {
+        Statement::Return(..) | Statement::Throw(_) => true,
+        Statement::Block(block) => block.statements.iter().any(returns),
+        Statement::Conditional(conditional) => {
+            returns(&conditional.success) && conditional.failure.as_deref().is_some_and(returns)
+        }
+        Statement::Iteration(iteration) => {
+            iteration.cond == Expression::Literal(Literal::Primary(Primary::Boolean(true)))
+                && !breaks(&iteration.body)
+        }
+        Statement::Match(matching) => matching.arms.iter().all(|arm| returns(&arm.body)),
+        Statement::Try(try_statement) => {
+            try_statement.finally.as_deref().is_some_and(returns)
+                || (returns(&try_statement.body)
+                    && try_statement
+                        .catch
+                        .as_ref()
+                        .is_none_or(|catch| returns(&catch.body)))
+        }
+        _ => false,
+    }
+}
+
+/// Whether a loop body contains a `break` out of the loop.
+fn breaks(statement: &Statement) -> bool {
+    match statement {
+        Statement::Break(_) => true,
+        Statement::Block(block) => block.statements.iter().any(breaks),
+        Statement::Conditional(conditional) => {
+            breaks(&conditional.success) || conditional.failure.as_deref().is_some_and(breaks)
+        }
+        Statement::Match(matching) => matching.arms.iter().any(|arm| breaks(&arm.body)),
+        Statement::Try(try_statement) => {
+            breaks(&try_statement.body)
+                || try_statement
+                    .catch
+                    .as_ref()
+                    .is_some_and(|catch| breaks(&catch.body))
+                || try_statement.finally.as_deref().is_some_and(breaks)
+        }
+        _ => false,
+    }
+}
+
+/// Describes an expression in an error message.
+fn describe(expression: &Expression) -> String {
+    match expression {
+        Expression::Literal(Literal::Variable(variable)) => format!("'{}'", variable.name),
+        Expression::Literal(Literal::Primary(Primary::Nil)) => "nil".to_string(),
+        Expression::Grouping(expression) => describe(expression),
+        Expression::Call(call) => format!("the result of {}", describe(&call.callee)),
+        Expression::Get(get) => format!("property '{}'", get.name),
+        _ => "a value".to_string(),
+    }
+}
+
+/// A variable without an annotation, which can hold any value.
+fn untyped() -> Symbol {
+    Symbol::Variable {
+        annotation: None,
+        non_nil: false,
+    }
+}
+
+fn variable(name: &Rc<String>, position: Position) -> Expression {
+    Expression::Literal(Literal::Variable(Variable::new(name.clone(), position)))
+}
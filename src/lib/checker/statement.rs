@@ -0,0 +1,343 @@
+use super::{
+    is_nullable, named, non_nil, untyped, Checkable, Checker, Inferable, Signature, Symbol,
+};
+use crate::ast::Statement::*;
+use crate::ast::{
+    self, Assignments, Block, Class, Conditional, Declaration, Destructuring, ForIn, Function,
+    ImportNames, Iteration, Literal, Match, Pattern, Position, Primary, Statement, Try, Type,
+};
+use std::rc::Rc;
+
+impl Checkable for Statement {
+    fn check(&mut self, checker: &mut Checker) {
+        match self {
+            Expression(expression) | Print(expression) | Throw(expression) => {
+                expression.infer(checker);
+            }
+            Declaration(declaration) => declaration.check(checker),
+            Destructuring(destructuring) => destructuring.check(checker),
+            Assignments(assignments) => assignments.check(checker),
+            Function(function) => function.check(checker),
+            Class(class) => class.check(checker),
+            Block(block) => block.check(checker),
+            Conditional(conditional) => conditional.check(checker),
+            Iteration(iteration) => iteration.check(checker),
+            ForIn(for_in) => for_in.check(checker),
+            Match(matching) => matching.check(checker),
+            Try(try_statement) => try_statement.check(checker),
+            Import(import) => {
+                let names = match &import.names {
+                    ImportNames::Module(name) => vec![name.clone()],
+                    ImportNames::Exports(names) => names.clone(),
+                };
+                for name in names {
+                    checker.classes.insert(name.clone());
+                    checker.declare(name, untyped());
+                }
+            }
+            Export(declaration) => declaration.check(checker),
+            Return(expression, position) => check_result(checker, expression, *position, false),
+            Yield(expression, position) => check_result(checker, expression, *position, true),
+            Break(_) | Continue(_) => (),
+        }
+    }
+}
+
+/// Checks a returned or yielded value against the annotated return type of
+/// the enclosing function. The return type of a generator is the type of
+/// the values it yields, so its `return` statements aren't checked.
+fn check_result(
+    checker: &mut Checker,
+    expression: &mut Option<ast::Expression>,
+    position: Position,
+    yielded: bool,
+) {
+    checker.position = position;
+    let annotated = match &checker.function {
+        Some(function) if function.generator == yielded => function
+            .returns
+            .clone()
+            .map(|returns| (function.name.clone(), returns)),
+        _ => None,
+    };
+    let (name, returns) = match annotated {
+        Some(annotated) => annotated,
+        None => {
+            if let Some(expression) = expression {
+                expression.infer(checker);
+            }
+            return;
+        }
+    };
+
+    let context = if yielded {
+        format!("Yielded value of {}", name)
+    } else {
+        format!("Return value of {}", name)
+    };
+    let value = expression.get_or_insert(nil());
+    checker.expect(&returns, value, &context, position);
+    checker.enforce(value, &returns, &context);
+}
+
+impl Checkable for Declaration {
+    fn check(&mut self, checker: &mut Checker) {
+        checker.position = self.position;
+        checker.annotated(&self.annotation);
+        let context = format!("Variable '{}'", self.name);
+        let found = match (&self.annotation, &mut self.value) {
+            (Some(annotation), value) => {
+                let value = value.get_or_insert(nil());
+                let found = checker.expect(annotation, value, &context, self.position);
+                checker.enforce(value, annotation, &context);
+                found
+            }
+            (None, Some(value)) => value.infer(checker),
+            (None, None) => named("nil"),
+        };
+
+        checker.declare(
+            self.name.clone(),
+            Symbol::Variable {
+                annotation: self.annotation.clone(),
+                non_nil: !is_nullable(&found),
+            },
+        );
+    }
+}
+
+impl Checkable for Destructuring {
+    fn check(&mut self, checker: &mut Checker) {
+        checker.position = self.position;
+        self.value.infer(checker);
+        for name in self.target.names() {
+            checker.declare(name.clone(), untyped());
+        }
+    }
+}
+
+impl Checkable for Assignments {
+    fn check(&mut self, checker: &mut Checker) {
+        let annotations: Vec<_> = self
+            .targets
+            .iter()
+            .map(|target| match target {
+                ast::Expression::Literal(Literal::Variable(variable)) => checker
+                    .annotation(&variable.name)
+                    .map(|annotation| (variable.name.clone(), annotation, variable.position)),
+                _ => None,
+            })
+            .collect();
+
+        // a list literal unpacked into the targets is checked item by item,
+        // while the items of any other list are only checked in strict mode
+        let unpacked = self.values.len() != self.targets.len();
+        let found = match self.values.as_mut_slice() {
+            [ast::Expression::List(items)] if unpacked && items.len() == annotations.len() => {
+                expect_each(checker, items, &annotations)
+            }
+            [value] if unpacked => {
+                value.infer(checker);
+                if checker.strict {
+                    self.checks = annotations
+                        .iter()
+                        .map(|annotation| {
+                            annotation.as_ref().map(|(name, annotation, _)| {
+                                (annotation.clone(), format!("Variable '{}'", name))
+                            })
+                        })
+                        .collect();
+                }
+                // items unpacked from a list may be nil
+                vec![]
+            }
+            values => expect_each(checker, values, &annotations),
+        };
+
+        for (index, target) in self.targets.iter_mut().enumerate() {
+            match target {
+                ast::Expression::Literal(Literal::Variable(variable)) => {
+                    let non_nil = found.get(index).is_some_and(|found| !is_nullable(found));
+                    checker.assigned(&variable.name, non_nil);
+                }
+                ast::Expression::Get(get) => {
+                    let object = get.object.infer(checker);
+                    let usage = format!("set property '{}' of", get.name);
+                    checker.check_nil(&get.object, &object, &usage);
+                }
+                ast::Expression::Index(index) => {
+                    let object = index.object.infer(checker);
+                    checker.check_nil(&index.object, &object, "index");
+                    index.index.infer(checker);
+                }
+                target => {
+                    target.infer(checker);
+                }
+            }
+        }
+    }
+}
+
+/// Checks values against the annotations of the variables they are assigned
+/// to, returning the type of each value.
+fn expect_each(
+    checker: &mut Checker,
+    values: &mut [ast::Expression],
+    annotations: &[Option<(Rc<String>, Type, Position)>],
+) -> Vec<Type> {
+    let mut found = vec![];
+    for (value, annotation) in values.iter_mut().zip(annotations) {
+        found.push(match annotation {
+            Some((name, annotation, position)) => {
+                let context = format!("Variable '{}'", name);
+                let found = checker.expect(annotation, value, &context, *position);
+                checker.enforce(value, annotation, &context);
+                found
+            }
+            None => value.infer(checker),
+        });
+    }
+    found
+}
+
+impl Checkable for Function {
+    fn check(&mut self, checker: &mut Checker) {
+        let result = if self.generator {
+            named("generator")
+        } else {
+            self.returns.clone().unwrap_or_else(|| named("any"))
+        };
+        let signature = Signature {
+            name: self.name.clone(),
+            params: self
+                .params
+                .iter()
+                .map(|param| (param.name.clone(), param.annotation.clone()))
+                .collect(),
+            result,
+        };
+        checker.declare(self.name.clone(), Symbol::Function(Rc::new(signature)));
+        checker.check_function(self);
+    }
+}
+
+impl Checkable for Class {
+    fn check(&mut self, checker: &mut Checker) {
+        let superclass = match &mut self.superclass {
+            Some(superclass) => {
+                superclass.infer(checker);
+                match superclass {
+                    ast::Expression::Literal(Literal::Variable(variable)) => {
+                        Some(variable.name.clone())
+                    }
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+        checker.position = self.position;
+        checker.classes.insert(self.name.clone());
+        checker.declare(self.name.clone(), Symbol::Class(superclass));
+
+        for method in &mut self.methods {
+            checker.check_function(Rc::make_mut(method));
+        }
+    }
+}
+
+impl Checkable for Block {
+    fn check(&mut self, checker: &mut Checker) {
+        checker.begin_scope();
+        checker.check_statements(&mut self.statements);
+        checker.end_scope();
+    }
+}
+
+impl Checkable for Conditional {
+    fn check(&mut self, checker: &mut Checker) {
+        self.cond.infer(checker);
+
+        checker.begin_scope();
+        checker.narrow(non_nil(&self.cond, true));
+        self.success.check(checker);
+        checker.end_scope();
+
+        if let Some(failure) = &mut self.failure {
+            checker.begin_scope();
+            checker.narrow(non_nil(&self.cond, false));
+            failure.check(checker);
+            checker.end_scope();
+        }
+    }
+}
+
+impl Checkable for Iteration {
+    fn check(&mut self, checker: &mut Checker) {
+        self.cond.infer(checker);
+
+        checker.begin_scope();
+        checker.narrow(non_nil(&self.cond, true));
+        self.body.check(checker);
+        if let Some(increment) = &mut self.increment {
+            increment.infer(checker);
+        }
+        checker.end_scope();
+    }
+}
+
+impl Checkable for ForIn {
+    fn check(&mut self, checker: &mut Checker) {
+        let iterable = self.iterable.infer(checker);
+        checker.check_nil(&self.iterable, &iterable, "iterate over");
+        if let "int" | "float" | "number" | "bool" | "function" | "class" | "module" =
+            iterable.name.as_str()
+        {
+            let message = format!("Cannot iterate over a value of type {}", iterable);
+            checker.error(self.position, message);
+        }
+
+        checker.begin_scope();
+        checker.declare(self.name.clone(), untyped());
+        self.body.check(checker);
+        checker.end_scope();
+    }
+}
+
+impl Checkable for Match {
+    fn check(&mut self, checker: &mut Checker) {
+        self.value.infer(checker);
+
+        for arm in &mut self.arms {
+            checker.begin_scope();
+            for pattern in &arm.patterns {
+                if let Pattern::Binding(name) = pattern {
+                    checker.declare(name.clone(), untyped());
+                }
+            }
+            if let Some(guard) = &mut arm.guard {
+                guard.infer(checker);
+            }
+            arm.body.check(checker);
+            checker.end_scope();
+        }
+    }
+}
+
+impl Checkable for Try {
+    fn check(&mut self, checker: &mut Checker) {
+        self.body.check(checker);
+        if let Some(catch) = &mut self.catch {
+            checker.begin_scope();
+            checker.declare(catch.name.clone(), untyped());
+            catch.body.check(checker);
+            checker.end_scope();
+        }
+        if let Some(finally) = &mut self.finally {
+            finally.check(checker);
+        }
+    }
+}
+
+fn nil() -> ast::Expression {
+    ast::Expression::Literal(Literal::Primary(Primary::Nil))
+}
@@ -1,31 +1,115 @@
 extern crate itertools;
+extern crate unicode_ident;
 
 #[macro_use]
 mod utils;
 mod ast;
+mod checker;
 mod parser;
+mod resolver;
 mod runner;
 mod scanner;
 mod tokens;
 
 use self::ast::Program;
 use self::runner::environment::Environment;
+use self::runner::native;
 use self::scanner::Scanner;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
+/// How the type annotations of a program are treated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Typing {
+    /// Annotations are ignored.
+    Dynamic,
+    /// Annotations are checked before the program runs.
+    Checked,
+    /// Annotations are checked before the program runs, and enforced by
+    /// runtime checks while it runs.
+    Strict,
+}
+
+/// Runs a program, resolving its imports relative to the working directory.
 pub fn run(code: &str) {
-    let mut scanner = Scanner::new(code);
-    let (tokens, errors) = scanner.tokenize();
+    let mut env = Environment::new(PathBuf::from("."), Typing::Dynamic);
+    define_globals(&mut env);
+    interpret(code, &mut env);
+}
+
+/// Runs the program in the file at `path`, resolving its imports relative to
+/// the file.
+pub fn run_file(path: &str, typing: Typing) -> io::Result<()> {
+    let code = fs::read_to_string(path)?;
+    let path = Path::new(path).canonicalize()?;
+    let dir = path.parent().map(PathBuf::from).unwrap_or_default();
+
+    let mut env = Environment::new(dir, typing);
+    // an import of the main file is a cycle
+    env.modules().borrow_mut().insert(path, None);
+    define_globals(&mut env);
+    interpret(&code, &mut env);
+    Ok(())
+}
+
+/// Type checks the program in the file at `path` without running it,
+/// printing any errors. Returns whether the program is free of errors.
+pub fn check_file(path: &str) -> io::Result<bool> {
+    let code = fs::read_to_string(path)?;
+    match compile(&code, Typing::Checked) {
+        Err(errors) => {
+            for err in errors {
+                eprintln!("{}", err);
+            }
+            Ok(false)
+        }
+        Ok(_) => Ok(true),
+    }
+}
 
-    let mut env = Environment::new();
-    match Program::parse(&mut tokens.into_iter().peekable()) {
-        Err(errs) => {
-            for err in errors.iter().chain(errs.iter()) {
+fn interpret(code: &str, env: &mut Environment) {
+    match compile(code, env.typing()) {
+        Err(errors) => {
+            for err in errors {
                 eprintln!("{}", err);
             }
         }
-        Ok(program) => match program.run(&mut env) {
-            Err(err) => eprintln!("{}", err),
-            _ => (),
-        },
+        Ok(program) => {
+            if let Err(err) = program.run(env) {
+                eprintln!("{}", err)
+            }
+        }
+    }
+}
+
+/// Scans, parses, type checks unless `typing` is dynamic, and resolves a
+/// program, collecting the errors of every stage that was reached.
+fn compile(code: &str, typing: Typing) -> Result<Program, Vec<String>> {
+    let mut scanner = Scanner::new(code);
+    let (tokens, errors) = scanner.tokenize();
+
+    let program = Program::parse(&mut tokens.into_iter().peekable()).and_then(|mut program| {
+        if typing != Typing::Dynamic {
+            program.check(typing == Typing::Strict)?;
+        }
+        program.resolve()?;
+        Ok(program)
+    });
+
+    match program {
+        Err(errs) => Err(errors.iter().cloned().chain(errs).collect()),
+        Ok(_) if !errors.is_empty() => Err(errors.to_vec()),
+        Ok(program) => Ok(program),
+    }
+}
+
+/// Declares the builtin functions and runs the prelude in a new global scope.
+fn define_globals(env: &mut Environment) {
+    native::define_globals(env);
+    interpret(native::PRELUDE, env);
+    if let Some(class) = env.get_local(&Rc::new("Error".to_string())) {
+        env.set_error_class(class);
     }
 }
@@ -1,8 +1,10 @@
 use super::utils;
 use crate::ast::{
-    Assignment, Binary, Call, Expression, Literal, Logical, LogicalOp, Primary, Unary,
+    Assignment, Assignments, Binary, BinaryOp, Block, Call, Expression, Function, Get, Index,
+    Literal, Logical, LogicalOp, Parameter, Position, Primary, Set, SetIndex, Statement, Super,
+    Ternary, Unary, Update, Variable,
 };
-use crate::tokens::{Token, TokenType::*};
+use crate::tokens::{Token, TokenType, TokenType::*};
 use std::iter::Peekable;
 use std::rc::Rc;
 
@@ -15,12 +17,14 @@ macro_rules! make_function {
             let mut expr = $fun(tokens)?;
 
             while Some(true) == check_next_token!(tokens, $($x),+) {
-                let op = utils::map_binary_op(tokens.next().unwrap().token_type);
+                let position = utils::position(tokens);
+                let op = utils::map_binary_op(tokens.next().unwrap().token_type)?;
                 let right = $fun(tokens)?;
                 expr = Expression::Binary(Binary{
-                    op: op,
+                    op,
                     left: Box::new(expr),
                     right: Box::new(right),
+                    position,
                 });
             }
 
@@ -37,7 +41,7 @@ impl Expression {
 
 impl Assignment {
     fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Expression, String> {
-        let expr = Logical::parse(tokens)?;
+        let expr = Ternary::parse(tokens)?;
 
         if match_next_token!(tokens, EQUAL) {
             let value = Assignment::parse(tokens)?;
@@ -45,16 +49,156 @@ impl Assignment {
             return Ok(match expr {
                 Expression::Literal(Literal::Variable(variable)) => {
                     Expression::Assignment(Assignment {
-                        variable: variable,
+                        variable,
                         value: Box::new(value),
                     })
                 }
-                _ => return Err(format!("Invalid assignment target")),
+                Expression::Get(get) => Expression::Set(Set {
+                    object: get.object,
+                    name: get.name,
+                    value: Box::new(value),
+                }),
+                Expression::Index(index) => Expression::SetIndex(SetIndex {
+                    object: index.object,
+                    index: index.index,
+                    value: Box::new(value),
+                }),
+                _ => return Err("Invalid assignment target".to_string()),
             });
         }
 
+        if Some(true)
+            == check_next_token!(
+                tokens,
+                PLUS_EQUAL,
+                HYPHEN_EQUAL,
+                ASTERICS_EQUAL,
+                SLASH_EQUAL
+            )
+        {
+            let position = utils::position(tokens);
+            let op = utils::map_binary_op(tokens.next().unwrap().token_type)?;
+            let value = Assignment::parse(tokens)?;
+            return Update::build(expr, op, value, false, position);
+        }
+
         Ok(expr)
     }
+
+    /// Parses the rest of `a, b = b, a;` after the first target and comma.
+    /// There must be as many values as targets, unless there's one value.
+    pub fn parse_multiple<T>(
+        tokens: &mut Peekable<T>,
+        first: Expression,
+    ) -> Result<Statement, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let mut targets = vec![first];
+        loop {
+            targets.push(Ternary::parse(tokens)?);
+            if !match_next_token!(tokens, COMMA) {
+                break;
+            }
+        }
+
+        for target in &targets {
+            match target {
+                Expression::Literal(Literal::Variable(_))
+                | Expression::Get(_)
+                | Expression::Index(_) => (),
+                _ => return Err("Invalid assignment target".to_string()),
+            }
+        }
+
+        let (line, offset) = match tokens.peek() {
+            Some(token) => (token.line, token.utf16),
+            None => (0, 0),
+        };
+        utils::consume(tokens, EQUAL, "Expect '=' after assignment targets")?;
+
+        let mut values = vec![];
+        loop {
+            values.push(Expression::parse(tokens)?);
+            if !match_next_token!(tokens, COMMA) {
+                break;
+            }
+        }
+        utils::consume(tokens, SEMICOLON, "Expect ';' after assignment")?;
+
+        if values.len() > 1 && values.len() != targets.len() {
+            return Err(format!(
+                "L{}:{} Cannot assign {} values to {} targets",
+                line,
+                offset,
+                values.len(),
+                targets.len()
+            ));
+        }
+
+        Ok(Statement::Assignments(Assignments {
+            targets,
+            values,
+            checks: vec![],
+        }))
+    }
+}
+
+impl Update {
+    /// Builds an update of `target`, reporting an invalid target at the
+    /// position of the operator.
+    fn build(
+        target: Expression,
+        op: BinaryOp,
+        value: Expression,
+        postfix: bool,
+        position: Position,
+    ) -> Result<Expression, String> {
+        match target {
+            Expression::Literal(Literal::Variable(_))
+            | Expression::Get(_)
+            | Expression::Index(_) => Ok(Expression::Update(Update {
+                target: Box::new(target),
+                op,
+                value: Box::new(value),
+                postfix,
+            })),
+            _ => Err(format!("{} Invalid assignment target", position)),
+        }
+    }
+
+    /// Turns `++` or `--` applied to `target` into `+= 1` or `-= 1`.
+    fn step(
+        token: TokenType,
+        target: Expression,
+        postfix: bool,
+        position: Position,
+    ) -> Result<Expression, String> {
+        let one = Expression::Literal(Literal::Primary(Primary::Integer(1)));
+        Update::build(target, utils::map_binary_op(token)?, one, postfix, position)
+    }
+}
+
+impl Ternary {
+    /// Parses `cond ? success : failure`. The failure branch is parsed as
+    /// another ternary, so `a ? b : c ? d : e` groups as `a ? b : (c ? d : e)`.
+    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Expression, String> {
+        let cond = Logical::parse(tokens)?;
+
+        if !match_next_token!(tokens, QUESTION) {
+            return Ok(cond);
+        }
+
+        let success = Expression::parse(tokens)?;
+        utils::consume(tokens, COLON, "Expect ':' after then branch of '?'")?;
+        let failure = Ternary::parse(tokens)?;
+
+        Ok(Expression::Ternary(Ternary {
+            cond: Box::new(cond),
+            success: Box::new(success),
+            failure: Box::new(failure),
+        }))
+    }
 }
 
 impl Logical {
@@ -92,6 +236,23 @@ impl Logical {
     }
 }
 
+/// Binary operators, from the loosest binding to the tightest:
+///
+/// | Operators              | Associativity |
+/// |------------------------|---------------|
+/// | `==` `!=`              | left          |
+/// | `<` `<=` `>` `>=`      | left          |
+/// | `\|`                   | left          |
+/// | `^`                    | left          |
+/// | `&`                    | left          |
+/// | `<<` `>>`              | left          |
+/// | `+` `-`                | left          |
+/// | `*` `/` `~/` `%`       | left          |
+/// | unary `!` `-` `~`      | right         |
+/// | `**`                   | right         |
+///
+/// `**` binds tighter than a unary operator on its left, so `-2 ** 2` is -4,
+/// while its right operand may itself be unary, as in `2 ** -1`.
 impl Binary {
     fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Expression, String> {
         Self::equality(tokens)
@@ -100,34 +261,77 @@ impl Binary {
     make_function!(equality, Self::comparison, EQUAL_EQUAL, BANG_EQUAL);
     make_function!(
         comparison,
-        Self::addition,
+        Self::bitwise_or,
         GREATER,
         GREATER_EQUAL,
         LESS,
         LESS_EQUAL
     );
+    make_function!(bitwise_or, Self::bitwise_xor, PIPE);
+    make_function!(bitwise_xor, Self::bitwise_and, CARET);
+    make_function!(bitwise_and, Self::shift, AMPERSAND);
+    make_function!(shift, Self::addition, LESS_LESS, GREATER_GREATER);
     make_function!(addition, Self::multiplication, PLUS, HYPHEN);
-    make_function!(multiplication, Unary::parse, SLASH, ASTERICS);
+    make_function!(
+        multiplication,
+        Unary::parse,
+        SLASH,
+        TILDE_SLASH,
+        ASTERICS,
+        PERCENT
+    );
+
+    fn power<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Expression, String> {
+        let mut expr = Call::parse(tokens)?;
+
+        if Some(true) == check_next_token!(tokens, PLUS_PLUS, HYPHEN_HYPHEN) {
+            let position = utils::position(tokens);
+            let token = tokens.next().unwrap().token_type;
+            expr = Update::step(token, expr, true, position)?;
+        }
+
+        let position = utils::position(tokens);
+        if match_next_token!(tokens, ASTERICS_ASTERICS) {
+            let right = Unary::parse(tokens)?;
+            return Ok(Expression::Binary(Binary {
+                op: BinaryOp::Power,
+                left: Box::new(expr),
+                right: Box::new(right),
+                position,
+            }));
+        }
+
+        Ok(expr)
+    }
 }
 
 impl Unary {
     fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Expression, String> {
         use crate::ast::UnaryOp::*;
 
-        if Some(true) == check_next_token!(tokens, BANG, HYPHEN) {
-            let op = if tokens.next().unwrap().token_type == BANG {
-                Not
-            } else {
-                Minus
+        if Some(true) == check_next_token!(tokens, PLUS_PLUS, HYPHEN_HYPHEN) {
+            let position = utils::position(tokens);
+            let token = tokens.next().unwrap().token_type;
+            let target = Self::parse(tokens)?;
+            return Update::step(token, target, false, position);
+        }
+
+        if Some(true) == check_next_token!(tokens, BANG, HYPHEN, TILDE) {
+            let position = utils::position(tokens);
+            let op = match tokens.next().unwrap().token_type {
+                BANG => Not,
+                HYPHEN => Minus,
+                _ => BitNot,
             };
             let expression = Self::parse(tokens)?;
             return Ok(Expression::Unary(Unary {
-                op: op,
+                op,
                 expression: Box::new(expression),
+                position,
             }));
         }
 
-        Ok(Call::parse(tokens)?)
+        Binary::power(tokens)
     }
 }
 
@@ -135,14 +339,36 @@ impl Call {
     fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Expression, String> {
         let mut expr = Literal::parse(tokens)?;
 
-        while match_next_token!(tokens, LEFT_PAREN) {
-            expr = Self::finish(tokens, expr)?;
+        loop {
+            let position = utils::position(tokens);
+            if match_next_token!(tokens, LEFT_PAREN) {
+                expr = Self::finish(tokens, expr, position)?;
+            } else if match_next_token!(tokens, LEFT_BRACKET) {
+                let index = Expression::parse(tokens)?;
+                utils::consume(tokens, RIGHT_BRACKET, "Expect ']' after index")?;
+                expr = Expression::Index(Index {
+                    object: Box::new(expr),
+                    index: Box::new(index),
+                });
+            } else if match_next_token!(tokens, DOT) {
+                let name = utils::get_identifier(tokens)?;
+                expr = Expression::Get(Get {
+                    object: Box::new(expr),
+                    name: Rc::new(name),
+                });
+            } else {
+                break;
+            }
         }
 
         Ok(expr)
     }
 
-    fn finish<T>(tokens: &mut Peekable<T>, expr: Expression) -> Result<Expression, String>
+    fn finish<T>(
+        tokens: &mut Peekable<T>,
+        expr: Expression,
+        position: Position,
+    ) -> Result<Expression, String>
     where
         T: Iterator<Item = Token>,
     {
@@ -159,6 +385,7 @@ impl Call {
         Ok(Expression::Call(Call {
             callee: Box::new(expr),
             arguments: args,
+            position,
         }))
     }
 }
@@ -168,31 +395,223 @@ impl Literal {
         use self::Literal::*;
         use self::Primary::*;
 
-        if tokens.peek() == None {
-            return Err(format!("Expected a literal. Found EOF"));
+        if tokens.peek().is_none() {
+            return Err("Expected a literal. Found EOF".to_string());
         } else if Some(true) == check_next_token!(tokens, SEMICOLON) {
             let token = tokens.peek().unwrap();
             return Err(format!(
                 "L{}:{} {}",
-                token.line, token.offset, "Expected a literal. Found ';'"
+                token.line, token.utf16, "Expected a literal. Found ';'"
             ));
         }
 
+        let position = utils::position(tokens);
         let literal = match tokens.next().unwrap().token_type {
-            IDENTIFIER(identifier) => Variable(Rc::new(identifier)),
+            IDENTIFIER(identifier) => Variable(self::Variable::new(Rc::new(identifier), position)),
+            THIS => This(self::Variable::new(Rc::new("this".to_string()), position)),
+            SUPER => {
+                utils::consume(tokens, DOT, "Expect '.' after 'super'")?;
+                let method = utils::get_identifier(tokens)?;
+                return Ok(Expression::Super(Super {
+                    variable: self::Variable::new(Rc::new("super".to_string()), position),
+                    method: Rc::new(method),
+                }));
+            }
+            INTEGER(num) => Primary(Integer(num)),
             NUMBER(num) => Primary(Number(num)),
             STRING(s) => Primary(String(s)),
             TRUE => Primary(Boolean(true)),
             FALSE => Primary(Boolean(false)),
             NIL => Primary(Nil),
-            LEFT_PAREN => {
-                let expr = Expression::parse(tokens)?;
-                utils::consume(tokens, RIGHT_PAREN, "Expect ) after expression")?;
-                return Ok(Expression::Grouping(Box::new(expr)));
+            FUN => {
+                let function = Function::parse_lambda(tokens, position)?;
+                return Ok(Expression::Lambda(Rc::new(function)));
             }
-            _ => return Err(format!("Unexpected character")),
+            LEFT_PAREN => return Self::parse_grouping(tokens, position),
+            LEFT_BRACKET => return Self::parse_list(tokens),
+            LEFT_BRACE => return Self::parse_map(tokens),
+            INTERPOLATION(string) => return Self::parse_interpolation(tokens, string),
+            _ => return Err("Unexpected character".to_string()),
         };
 
         Ok(Expression::Literal(literal))
     }
 }
+
+impl Literal {
+    /// Parses a parenthesized expression, which turns out to be the parameter
+    /// list of an arrow function if it is followed by `=>`.
+    fn parse_grouping<T>(tokens: &mut Peekable<T>, position: Position) -> Result<Expression, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        if match_next_token!(tokens, RIGHT_PAREN) {
+            return Function::parse_arrow(tokens, vec![], None, position);
+        }
+
+        let mut exprs = vec![];
+        let mut rest = None;
+        loop {
+            if match_next_token!(tokens, ELLIPSIS) {
+                rest = Some(Rc::new(utils::get_identifier(tokens)?));
+                break;
+            }
+            exprs.push(Expression::parse(tokens)?);
+            if !match_next_token!(tokens, COMMA) {
+                break;
+            }
+        }
+        utils::consume(tokens, RIGHT_PAREN, "Expect ) after expression")?;
+
+        if exprs.len() == 1 && rest.is_none() && Some(true) != check_next_token!(tokens, ARROW) {
+            return Ok(Expression::Grouping(Box::new(exprs.remove(0))));
+        }
+
+        Function::parse_arrow(tokens, exprs, rest, position)
+    }
+}
+
+impl Literal {
+    fn parse_list<T>(tokens: &mut Peekable<T>) -> Result<Expression, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let mut items = vec![];
+        while Some(false) == check_next_token!(tokens, RIGHT_BRACKET) {
+            items.push(Expression::parse(tokens)?);
+            if !match_next_token!(tokens, COMMA) {
+                break;
+            }
+        }
+        utils::consume(tokens, RIGHT_BRACKET, "Expect ']' after list items")?;
+
+        Ok(Expression::List(items))
+    }
+
+    fn parse_map<T>(tokens: &mut Peekable<T>) -> Result<Expression, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let mut entries = vec![];
+        while Some(false) == check_next_token!(tokens, RIGHT_BRACE) {
+            let key = Expression::parse(tokens)?;
+            utils::consume(tokens, COLON, "Expect ':' after map key")?;
+            let value = Expression::parse(tokens)?;
+            entries.push((key, value));
+            if !match_next_token!(tokens, COMMA) {
+                break;
+            }
+        }
+        utils::consume(tokens, RIGHT_BRACE, "Expect '}' after map entries")?;
+
+        Ok(Expression::Map(entries))
+    }
+}
+
+impl Literal {
+    /// Parses the rest of an interpolated string, which the scanner splits
+    /// into `INTERPOLATION` tokens followed by the embedded expressions, up to
+    /// the closing `STRING` token.
+    fn parse_interpolation<T>(tokens: &mut Peekable<T>, first: String) -> Result<Expression, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let mut parts = vec![];
+        let mut string = first;
+
+        loop {
+            if !string.is_empty() {
+                parts.push(Expression::Literal(Literal::Primary(Primary::String(
+                    string,
+                ))));
+            }
+            parts.push(Expression::parse(tokens)?);
+
+            string = match tokens.next() {
+                Some(Token {
+                    token_type: INTERPOLATION(string),
+                    ..
+                }) => string,
+                Some(Token {
+                    token_type: STRING(string),
+                    ..
+                }) => {
+                    if !string.is_empty() {
+                        parts.push(Expression::Literal(Literal::Primary(Primary::String(
+                            string,
+                        ))));
+                    }
+                    return Ok(Expression::Interpolation(parts));
+                }
+                Some(token) => {
+                    return Err(format!(
+                        "L{}:{} Expect '}}' after interpolated expression",
+                        token.line, token.utf16
+                    ))
+                }
+                None => return Err("Unterminated string interpolation".to_string()),
+            };
+        }
+    }
+}
+
+impl Function {
+    fn parse_arrow<T>(
+        tokens: &mut Peekable<T>,
+        exprs: Vec<Expression>,
+        rest: Option<Rc<String>>,
+        position: Position,
+    ) -> Result<Expression, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        utils::consume(tokens, ARROW, "Expect '=>' after arrow function params")?;
+
+        let mut params: Vec<Parameter> = vec![];
+        for expr in exprs {
+            let (param, position) = match expr {
+                Expression::Literal(Literal::Variable(var)) => (
+                    Parameter {
+                        name: var.name,
+                        default: None,
+                        constant: false,
+                        annotation: None,
+                    },
+                    var.position,
+                ),
+                Expression::Assignment(assignment) => (
+                    Parameter {
+                        name: assignment.variable.name,
+                        default: Some(*assignment.value),
+                        constant: false,
+                        annotation: None,
+                    },
+                    assignment.variable.position,
+                ),
+                _ => return Err("Invalid arrow function parameter".to_string()),
+            };
+            utils::push_parameter(&mut params, param, position)?;
+        }
+
+        let body_position = utils::position(tokens);
+        let body = if match_next_token!(tokens, LEFT_BRACE) {
+            Block::parse(tokens)?
+        } else {
+            let value = Expression::parse(tokens)?;
+            Statement::Block(Block {
+                statements: vec![Statement::Return(Some(value), body_position)],
+            })
+        };
+
+        Ok(Expression::Lambda(Rc::new(Function {
+            name: Rc::new("lambda".to_string()),
+            params,
+            rest,
+            generator: body.yields(),
+            body: Box::new(body),
+            doc: None,
+            returns: None,
+            position,
+        })))
+    }
+}
@@ -11,7 +11,7 @@ impl Program {
         let mut statements = vec![];
         let mut errors = vec![];
 
-        while tokens.peek() != None {
+        while tokens.peek().is_some() {
             match Statement::parse(tokens) {
                 Ok(statement) => statements.push(statement),
                 Err(error) => {
@@ -21,12 +21,10 @@ impl Program {
             }
         }
 
-        if errors.len() != 0 {
+        if !errors.is_empty() {
             Err(errors)
         } else {
-            Ok(Program {
-                statements: statements,
-            })
+            Ok(Program { statements })
         }
     }
 }
@@ -34,7 +32,12 @@ impl Program {
 fn synchronize<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) {
     use self::TokenType::*;
 
-    while Some(false) == check_next_token!(tokens, CLASS, FUN, VAR, FOR, IF, WHILE, PRINT, RETURN) {
+    while Some(false)
+        == check_next_token!(
+            tokens, CLASS, FUN, VAR, CONST, FOR, IF, WHILE, PRINT, RETURN, BREAK, CONTINUE, MATCH,
+            THROW, TRY, IMPORT, EXPORT, YIELD
+        )
+    {
         if match_next_token!(tokens, SEMICOLON) {
             return;
         }
@@ -1,15 +1,26 @@
 use super::utils;
-use crate::ast::{Block, Conditional, Declaration, Expression, Function, Iteration, Statement};
-use crate::tokens::{Token, TokenType::*};
+use crate::ast::{
+    self, Arm, Assignment, Block, Catch, Class, Conditional, Declaration, Destructuring,
+    Expression, ForIn, Function, Import, ImportNames, Iteration, Match, Parameter, Pattern,
+    Position, Primary, Statement, Target, Try,
+};
+use crate::tokens::{Token, TokenType, TokenType::*};
 use std::iter::Peekable;
 use std::rc::Rc;
 
 impl Statement {
     pub fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Self, String> {
+        let doc = tokens.peek().and_then(|token| token.doc.clone());
+        let position = utils::position(tokens);
+
         if match_next_token!(tokens, VAR) {
-            Declaration::parse(tokens)
+            Declaration::parse(tokens, doc)
+        } else if match_next_token!(tokens, CONST) {
+            Declaration::parse_constant(tokens, doc)
         } else if match_next_token!(tokens, FUN) {
-            Function::parse(tokens)
+            Function::parse(tokens, doc)
+        } else if match_next_token!(tokens, CLASS) {
+            Class::parse(tokens, doc)
         } else if match_next_token!(tokens, PRINT) {
             Self::print(tokens)
         } else if match_next_token!(tokens, LEFT_BRACE) {
@@ -20,8 +31,28 @@ impl Statement {
             Iteration::parse(tokens)
         } else if match_next_token!(tokens, FOR) {
             Iteration::parse_for(tokens)
+        } else if match_next_token!(tokens, MATCH) {
+            Match::parse(tokens)
+        } else if match_next_token!(tokens, IMPORT) {
+            Import::parse(tokens)
+        } else if match_next_token!(tokens, EXPORT) {
+            Self::export(tokens, doc)
+        } else if match_next_token!(tokens, TRY) {
+            Try::parse(tokens)
+        } else if match_next_token!(tokens, THROW) {
+            let expr = Expression::parse(tokens)?;
+            utils::consume(tokens, SEMICOLON, "Expect ';' after throw statement")?;
+            Ok(Statement::Throw(expr))
         } else if match_next_token!(tokens, RETURN) {
-            Self::ret(tokens)
+            Self::ret(tokens, position)
+        } else if match_next_token!(tokens, YIELD) {
+            Self::yield_statement(tokens, position)
+        } else if match_next_token!(tokens, BREAK) {
+            utils::consume(tokens, SEMICOLON, "Expect ';' after 'break'")?;
+            Ok(Statement::Break(position))
+        } else if match_next_token!(tokens, CONTINUE) {
+            utils::consume(tokens, SEMICOLON, "Expect ';' after 'continue'")?;
+            Ok(Statement::Continue(position))
         } else {
             Self::expression(tokens)
         }
@@ -37,20 +68,91 @@ impl Statement {
 
     fn expression<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Self, String> {
         let expr = Expression::parse(tokens)?;
+        if match_next_token!(tokens, COMMA) {
+            return Assignment::parse_multiple(tokens, expr);
+        }
         utils::consume(tokens, SEMICOLON, "Expect ';' after statement")?;
         Ok(Statement::Expression(expr))
     }
 
-    fn ret<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Self, String> {
-        let expr = Expression::parse(tokens)?;
+    fn export<T>(tokens: &mut Peekable<T>, doc: Option<String>) -> Result<Self, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let declaration = if match_next_token!(tokens, VAR) {
+            Declaration::parse(tokens, doc)?
+        } else if match_next_token!(tokens, CONST) {
+            Declaration::parse_constant(tokens, doc)?
+        } else if match_next_token!(tokens, FUN) {
+            Function::parse(tokens, doc)?
+        } else if match_next_token!(tokens, CLASS) {
+            Class::parse(tokens, doc)?
+        } else {
+            return Err(match tokens.peek() {
+                Some(token) => format!(
+                    "L{}:{} Expect 'var', 'const', 'fun' or 'class' after 'export'",
+                    token.line, token.utf16
+                ),
+                None => {
+                    "Expect 'var', 'const', 'fun' or 'class' after 'export' found EOF".to_string()
+                }
+            });
+        };
+
+        Ok(Statement::Export(Box::new(declaration)))
+    }
+
+    fn ret<T>(tokens: &mut Peekable<T>, position: Position) -> Result<Self, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let expr = if Some(true) == check_next_token!(tokens, SEMICOLON) {
+            None
+        } else {
+            Some(Expression::parse(tokens)?)
+        };
         utils::consume(tokens, SEMICOLON, "Expect ';' after return statement")?;
-        Ok(Statement::Return(expr))
+        Ok(Statement::Return(expr, position))
+    }
+
+    fn yield_statement<T>(tokens: &mut Peekable<T>, position: Position) -> Result<Self, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let expr = if Some(true) == check_next_token!(tokens, SEMICOLON) {
+            None
+        } else {
+            Some(Expression::parse(tokens)?)
+        };
+        utils::consume(tokens, SEMICOLON, "Expect ';' after yield statement")?;
+        Ok(Statement::Yield(expr, position))
     }
 }
 
 impl Declaration {
-    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Statement, String> {
+    fn parse<T>(tokens: &mut Peekable<T>, doc: Option<String>) -> Result<Statement, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        if Some(true) == check_next_token!(tokens, LEFT_PAREN, LEFT_BRACKET, LEFT_BRACE) {
+            return Destructuring::parse(tokens, false);
+        }
+        let position = utils::position(tokens);
         let name = utils::get_identifier(tokens)?;
+        Self::parse_initializer(tokens, name, position, doc)
+    }
+
+    /// Parses the rest of a variable declaration after its name.
+    fn parse_initializer<T>(
+        tokens: &mut Peekable<T>,
+        name: String,
+        position: Position,
+        doc: Option<String>,
+    ) -> Result<Statement, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let annotation = utils::annotation(tokens)?;
         let mut value = None;
 
         if match_next_token!(tokens, EQUAL) {
@@ -60,38 +162,276 @@ impl Declaration {
         utils::consume(tokens, SEMICOLON, "Expect ';' after variable declaration")?;
         Ok(Statement::Declaration(Self {
             name: Rc::new(name),
-            value: value,
+            value,
+            constant: false,
+            annotation,
+            doc,
+            position,
         }))
     }
+
+    fn parse_constant<T>(tokens: &mut Peekable<T>, doc: Option<String>) -> Result<Statement, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        if Some(true) == check_next_token!(tokens, LEFT_PAREN, LEFT_BRACKET, LEFT_BRACE) {
+            return Destructuring::parse(tokens, true);
+        }
+        let position = utils::position(tokens);
+        let name = utils::get_identifier(tokens)?;
+        let annotation = utils::annotation(tokens)?;
+        let message = format!("Expect '=' after constant '{}'", name);
+        utils::consume(tokens, EQUAL, message)?;
+        let value = Expression::parse(tokens)?;
+
+        utils::consume(tokens, SEMICOLON, "Expect ';' after constant declaration")?;
+        Ok(Statement::Declaration(Self {
+            name: Rc::new(name),
+            value: Some(value),
+            constant: true,
+            annotation,
+            doc,
+            position,
+        }))
+    }
+}
+
+impl Destructuring {
+    fn parse<T>(tokens: &mut Peekable<T>, constant: bool) -> Result<Statement, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let position = utils::position(tokens);
+        let target = Target::parse(tokens)?;
+        utils::consume(tokens, EQUAL, format!("Expect '=' after {}", target))?;
+        let value = Expression::parse(tokens)?;
+
+        utils::consume(tokens, SEMICOLON, "Expect ';' after variable declaration")?;
+        Ok(Statement::Destructuring(Self {
+            target,
+            value,
+            constant,
+            position,
+        }))
+    }
+}
+
+impl Target {
+    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Self, String> {
+        if match_next_token!(tokens, LEFT_PAREN) {
+            Self::parse_list(tokens, RIGHT_PAREN, "Expect ')' after destructuring target")
+        } else if match_next_token!(tokens, LEFT_BRACKET) {
+            Self::parse_list(
+                tokens,
+                RIGHT_BRACKET,
+                "Expect ']' after destructuring target",
+            )
+        } else if match_next_token!(tokens, LEFT_BRACE) {
+            let mut names = vec![];
+            loop {
+                names.push(Rc::new(utils::get_identifier(tokens)?));
+                if !match_next_token!(tokens, COMMA) {
+                    break;
+                }
+            }
+            utils::consume(tokens, RIGHT_BRACE, "Expect '}' after field names")?;
+            Ok(Target::Fields(names))
+        } else {
+            Ok(Target::Name(Rc::new(utils::get_identifier(tokens)?)))
+        }
+    }
+
+    /// Parses the items of a list target, the last of which can be a rest
+    /// name such as `...rest`.
+    fn parse_list<T>(
+        tokens: &mut Peekable<T>,
+        close: TokenType,
+        message: &str,
+    ) -> Result<Self, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let mut items = vec![];
+        let mut rest = None;
+        loop {
+            if match_next_token!(tokens, ELLIPSIS) {
+                rest = Some(Rc::new(utils::get_identifier(tokens)?));
+                break;
+            }
+            items.push(Target::parse(tokens)?);
+            if !match_next_token!(tokens, COMMA) {
+                break;
+            }
+        }
+        utils::consume(tokens, close, message)?;
+
+        Ok(Target::List(items, rest))
+    }
 }
 
 impl Function {
-    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Statement, String> {
+    fn parse<T>(tokens: &mut Peekable<T>, doc: Option<String>) -> Result<Statement, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let mut function = Self::parse_function(tokens)?;
+        function.doc = doc;
+        Ok(Statement::Function(function))
+    }
+
+    fn parse_function<T>(tokens: &mut Peekable<T>) -> Result<Self, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let position = utils::position(tokens);
         let name = utils::get_identifier(tokens)?;
         utils::consume(tokens, LEFT_PAREN, "Expect '(' after function name")?;
+        Self::finish(tokens, Rc::new(name), position)
+    }
 
-        let mut params = vec![];
+    /// Parses an anonymous function after `fun`, which is at `position`.
+    pub(super) fn parse_lambda<T>(
+        tokens: &mut Peekable<T>,
+        position: Position,
+    ) -> Result<Self, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        utils::consume(tokens, LEFT_PAREN, "Expect '(' after 'fun'")?;
+        Self::finish(tokens, Rc::new("lambda".to_string()), position)
+    }
+
+    fn finish<T>(
+        tokens: &mut Peekable<T>,
+        name: Rc<String>,
+        position: Position,
+    ) -> Result<Self, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let mut params: Vec<Parameter> = vec![];
+        let mut rest = None;
         if Some(false) == check_next_token!(tokens, RIGHT_PAREN) {
-            params.push(utils::get_identifier(tokens)?);
-            while match_next_token!(tokens, COMMA) {
-                params.push(utils::get_identifier(tokens)?);
+            loop {
+                if match_next_token!(tokens, ELLIPSIS) {
+                    rest = Some(Rc::new(utils::get_identifier(tokens)?));
+                    utils::consume(tokens, RIGHT_PAREN, "Expect ')' after rest parameter")?;
+                    break;
+                }
+
+                let position = utils::position(tokens);
+                let param = Parameter::parse(tokens)?;
+                utils::push_parameter(&mut params, param, position)?;
+
+                if !match_next_token!(tokens, COMMA) {
+                    break;
+                }
             }
         }
-        utils::consume(tokens, RIGHT_PAREN, "Expect ')' after function params")?;
+        if rest.is_none() {
+            utils::consume(tokens, RIGHT_PAREN, "Expect ')' after function params")?;
+        }
+
+        let returns = if match_next_token!(tokens, THIN_ARROW) {
+            Some(utils::parse_type(tokens)?)
+        } else {
+            None
+        };
 
         utils::consume(tokens, LEFT_BRACE, "Expect '{' after function params")?;
         let body = Block::parse(tokens)?;
 
-        Ok(Statement::Function(Function {
-            name: Rc::new(name),
-            params: params.into_iter().map(|param| Rc::new(param)).collect(),
+        Ok(Function {
+            name,
+            params,
+            rest,
+            generator: body.yields(),
             body: Box::new(body),
+            doc: None,
+            returns,
+            position,
+        })
+    }
+}
+
+impl Parameter {
+    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Self, String> {
+        let constant = match_next_token!(tokens, CONST);
+        let name = utils::get_identifier(tokens)?;
+        let annotation = utils::annotation(tokens)?;
+        let mut default = None;
+
+        if match_next_token!(tokens, EQUAL) {
+            default = Some(Expression::parse(tokens)?);
+        }
+
+        Ok(Parameter {
+            name: Rc::new(name),
+            default,
+            constant,
+            annotation,
+        })
+    }
+}
+
+impl Class {
+    fn parse<T>(tokens: &mut Peekable<T>, doc: Option<String>) -> Result<Statement, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        use crate::ast::{Literal, Variable};
+
+        let position = utils::position(tokens);
+        let name = utils::get_identifier(tokens)?;
+
+        let mut superclass = None;
+        if match_next_token!(tokens, LESS) {
+            let (line, offset) = match tokens.peek() {
+                Some(token) => (token.line, token.utf16),
+                None => (0, 0),
+            };
+            let parent = utils::get_identifier(tokens)?;
+            if parent == name {
+                return Err(format!(
+                    "L{}:{} A class can't inherit from itself",
+                    line, offset
+                ));
+            }
+            let parent_position = Position {
+                line,
+                column: offset,
+            };
+            superclass = Some(Expression::Literal(Literal::Variable(Variable::new(
+                Rc::new(parent),
+                parent_position,
+            ))));
+        }
+
+        utils::consume(tokens, LEFT_BRACE, "Expect '{' before class body")?;
+
+        let mut methods = vec![];
+        while Some(false) == check_next_token!(tokens, RIGHT_BRACE) {
+            let doc = tokens.peek().and_then(|token| token.doc.clone());
+            let mut method = Function::parse_function(tokens)?;
+            method.doc = doc;
+            methods.push(Rc::new(method));
+        }
+        utils::consume(tokens, RIGHT_BRACE, "Expect '}' after class body")?;
+
+        Ok(Statement::Class(Class {
+            name: Rc::new(name),
+            superclass,
+            methods,
+            doc,
+            position,
         }))
     }
 }
 
 impl Block {
-    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Statement, String> {
+    pub(super) fn parse<T: Iterator<Item = Token>>(
+        tokens: &mut Peekable<T>,
+    ) -> Result<Statement, String> {
         let mut statements = vec![];
 
         while Some(false) == check_next_token!(tokens, RIGHT_BRACE) {
@@ -99,9 +439,7 @@ impl Block {
         }
         utils::consume(tokens, RIGHT_BRACE, "Expect } after block")?;
 
-        Ok(Statement::Block(Block {
-            statements: statements,
-        }))
+        Ok(Statement::Block(Block { statements }))
     }
 }
 
@@ -125,6 +463,235 @@ impl Conditional {
     }
 }
 
+impl Import {
+    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Statement, String> {
+        let names = if match_next_token!(tokens, LEFT_BRACE) {
+            let mut names = vec![Rc::new(utils::get_identifier(tokens)?)];
+            while match_next_token!(tokens, COMMA) {
+                names.push(Rc::new(utils::get_identifier(tokens)?));
+            }
+            utils::consume(tokens, RIGHT_BRACE, "Expect '}' after imported names")?;
+            Self::keyword(tokens, "from")?;
+            Some(names)
+        } else {
+            None
+        };
+
+        let path = match tokens.next() {
+            Some(Token {
+                token_type: STRING(path),
+                ..
+            }) => path,
+            Some(token) => {
+                return Err(format!(
+                    "L{}:{} Expect a module path string, found {:?}",
+                    token.line, token.utf16, token.token_type
+                ))
+            }
+            None => return Err("Expect a module path string, found EOF".to_string()),
+        };
+
+        let names = match names {
+            Some(names) => ImportNames::Exports(names),
+            None => {
+                Self::keyword(tokens, "as")?;
+                ImportNames::Module(Rc::new(utils::get_identifier(tokens)?))
+            }
+        };
+        utils::consume(tokens, SEMICOLON, "Expect ';' after import")?;
+
+        Ok(Statement::Import(Import { path, names }))
+    }
+
+    /// Consumes `as` or `from`, which are only keywords inside an import.
+    fn keyword<T>(tokens: &mut Peekable<T>, keyword: &str) -> Result<(), String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        match tokens.next() {
+            Some(Token {
+                token_type: IDENTIFIER(ref name),
+                ..
+            }) if name == keyword => Ok(()),
+            Some(token) => Err(format!(
+                "L{}:{} Expect '{}' in import, found {:?}",
+                token.line, token.utf16, keyword, token.token_type
+            )),
+            None => Err(format!("Expect '{}' in import, found EOF", keyword)),
+        }
+    }
+}
+
+impl Try {
+    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Statement, String> {
+        utils::consume(tokens, LEFT_BRACE, "Expect '{' after 'try'")?;
+        let body = Block::parse(tokens)?;
+
+        let mut catch = None;
+        if match_next_token!(tokens, CATCH) {
+            utils::consume(tokens, LEFT_PAREN, "Expect '(' after 'catch'")?;
+            let name = utils::get_identifier(tokens)?;
+            utils::consume(tokens, RIGHT_PAREN, "Expect ')' after catch variable")?;
+            utils::consume(tokens, LEFT_BRACE, "Expect '{' after catch clause")?;
+            catch = Some(Catch {
+                name: Rc::new(name),
+                body: Box::new(Block::parse(tokens)?),
+            });
+        }
+
+        let mut finally = None;
+        if match_next_token!(tokens, FINALLY) {
+            utils::consume(tokens, LEFT_BRACE, "Expect '{' after 'finally'")?;
+            finally = Some(Box::new(Block::parse(tokens)?));
+        }
+
+        if catch.is_none() && finally.is_none() {
+            return Err(match tokens.peek() {
+                Some(token) => format!(
+                    "L{}:{} Expect 'catch' or 'finally' after try block",
+                    token.line, token.utf16
+                ),
+                None => "Expect 'catch' or 'finally' after try block found EOF".to_string(),
+            });
+        }
+
+        Ok(Statement::Try(Try {
+            body: Box::new(body),
+            catch,
+            finally,
+        }))
+    }
+}
+
+impl Match {
+    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Statement, String> {
+        utils::consume(tokens, LEFT_PAREN, "Expect '(' after 'match'")?;
+        let value = Expression::parse(tokens)?;
+        utils::consume(tokens, RIGHT_PAREN, "Expect ')' after match value")?;
+        utils::consume(tokens, LEFT_BRACE, "Expect '{' before match arms")?;
+
+        let mut arms = vec![];
+        while Some(false) == check_next_token!(tokens, RIGHT_BRACE) {
+            arms.push(Arm::parse(tokens)?);
+            match_next_token!(tokens, COMMA);
+        }
+        utils::consume(tokens, RIGHT_BRACE, "Expect '}' after match arms")?;
+
+        Ok(Statement::Match(Match { value, arms }))
+    }
+}
+
+impl Arm {
+    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Self, String> {
+        let (line, offset) = match tokens.peek() {
+            Some(token) => (token.line, token.utf16),
+            None => (0, 0),
+        };
+
+        let mut patterns = vec![Pattern::parse(tokens)?];
+        while match_next_token!(tokens, PIPE) {
+            patterns.push(Pattern::parse(tokens)?);
+        }
+        let binds = patterns.iter().any(|p| matches!(p, Pattern::Binding(_)));
+        if patterns.len() > 1 && binds {
+            return Err(format!(
+                "L{}:{} Alternative patterns can't bind names",
+                line, offset
+            ));
+        }
+
+        let guard = if match_next_token!(tokens, IF) {
+            Some(Expression::parse(tokens)?)
+        } else {
+            None
+        };
+
+        utils::consume(tokens, ARROW, "Expect '=>' after pattern")?;
+        let body = Statement::parse(tokens)?;
+
+        Ok(Arm {
+            patterns,
+            guard,
+            body: Box::new(body),
+        })
+    }
+}
+
+impl Pattern {
+    fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Self, String> {
+        let (line, offset) = match tokens.peek() {
+            Some(token) => (token.line, token.utf16),
+            None => return Err("Expected a pattern, found EOF".to_string()),
+        };
+
+        if let Some(IDENTIFIER(name)) = tokens.peek().map(|token| &token.token_type) {
+            let pattern = match name.as_str() {
+                "_" => Pattern::Wildcard,
+                _ => Pattern::Binding(Rc::new(name.clone())),
+            };
+            tokens.next();
+            return Ok(pattern);
+        }
+
+        if match_next_token!(tokens, IS) {
+            let name = utils::get_identifier(tokens)?;
+            if !ast::TYPE_NAMES.contains(&name.as_str()) {
+                return Err(format!(
+                    "L{}:{} Unknown type '{}', expected one of {}",
+                    line,
+                    offset,
+                    name,
+                    ast::TYPE_NAMES.join(", ")
+                ));
+            }
+            return Ok(Pattern::Type(Rc::new(name)));
+        }
+
+        let start = Self::literal(tokens)?;
+        if !match_next_token!(tokens, DOT_DOT) {
+            return Ok(Pattern::Literal(start));
+        }
+
+        let end = Self::literal(tokens)?;
+        let numeric = |bound: &Primary| matches!(bound, Primary::Integer(_) | Primary::Number(_));
+        if numeric(&start) && numeric(&end) {
+            Ok(Pattern::Range(start, end))
+        } else {
+            Err(format!(
+                "L{}:{} Range pattern bounds must be numbers",
+                line, offset
+            ))
+        }
+    }
+
+    fn literal<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Primary, String> {
+        let negative = match_next_token!(tokens, HYPHEN);
+        let token = match tokens.next() {
+            Some(token) => token,
+            None => return Err("Expected a pattern, found EOF".to_string()),
+        };
+
+        let primary = match token.token_type {
+            INTEGER(integer) if negative => Primary::Integer(-integer),
+            NUMBER(number) if negative => Primary::Number(-number),
+            INTEGER(integer) => Primary::Integer(integer),
+            NUMBER(number) => Primary::Number(number),
+            STRING(string) if !negative => Primary::String(string),
+            TRUE if !negative => Primary::Boolean(true),
+            FALSE if !negative => Primary::Boolean(false),
+            NIL if !negative => Primary::Nil,
+            token_type => {
+                return Err(format!(
+                    "L{}:{} Expected a pattern, found {:?}",
+                    token.line, token.utf16, token_type
+                ))
+            }
+        };
+
+        Ok(primary)
+    }
+}
+
 impl Iteration {
     fn parse<T: Iterator<Item = Token>>(tokens: &mut Peekable<T>) -> Result<Statement, String> {
         utils::consume(tokens, LEFT_PAREN, "Expect '(' after 'while'")?;
@@ -133,8 +700,9 @@ impl Iteration {
         let body = Statement::parse(tokens)?;
 
         Ok(Statement::Iteration(Iteration {
-            cond: cond,
+            cond,
             body: Box::new(body),
+            increment: None,
         }))
     }
 
@@ -145,7 +713,21 @@ impl Iteration {
         let init = if match_next_token!(tokens, SEMICOLON) {
             None
         } else if match_next_token!(tokens, VAR) {
-            Some(Declaration::parse(tokens)?)
+            let position = utils::position(tokens);
+            let name = utils::get_identifier(tokens)?;
+            if let Some(Token {
+                token_type: IDENTIFIER(word),
+                ..
+            }) = tokens.peek()
+            {
+                if word == "in" {
+                    tokens.next();
+                    return ForIn::parse(tokens, name);
+                }
+            }
+            Some(Declaration::parse_initializer(
+                tokens, name, position, None,
+            )?)
         } else {
             Some(Statement::expression(tokens)?)
         };
@@ -164,25 +746,15 @@ impl Iteration {
         };
         utils::consume(tokens, RIGHT_PAREN, "Exprect ')' after for clauses")?;
 
-        let mut body = Statement::parse(tokens)?;
-
-        if let Some(inc) = increment {
-            body = Statement::Block(Block {
-                statements: vec![body, Statement::Expression(inc)],
-            })
-        }
+        let body = Statement::parse(tokens)?;
 
-        body = if let Some(c) = cond {
-            Statement::Iteration(Iteration {
-                cond: c,
-                body: Box::new(body),
-            })
-        } else {
-            Statement::Iteration(Iteration {
-                cond: Expression::Literal(Literal::Primary(Primary::Boolean(true))),
-                body: Box::new(body),
-            })
-        };
+        let mut body = Statement::Iteration(Iteration {
+            cond: cond.unwrap_or(Expression::Literal(Literal::Primary(Primary::Boolean(
+                true,
+            )))),
+            body: Box::new(body),
+            increment,
+        });
 
         if let Some(i) = init {
             body = Statement::Block(Block {
@@ -193,3 +765,108 @@ impl Iteration {
         Ok(body)
     }
 }
+
+impl ForIn {
+    /// Parses the rest of a `for (var name in iterable)` loop after `in`.
+    fn parse<T>(tokens: &mut Peekable<T>, name: String) -> Result<Statement, String>
+    where
+        T: Iterator<Item = Token>,
+    {
+        let position = utils::position(tokens);
+        let iterable = Expression::parse(tokens)?;
+        utils::consume(tokens, RIGHT_PAREN, "Expect ')' after for-in clause")?;
+        let body = Statement::parse(tokens)?;
+
+        Ok(Statement::ForIn(ForIn {
+            name: Rc::new(name),
+            iterable,
+            body: Box::new(body),
+            position,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{Program, Statement};
+    use crate::scanner::Scanner;
+
+    fn parse(code: &str) -> Vec<Statement> {
+        let mut scanner = Scanner::new(code);
+        let (tokens, errors) = scanner.tokenize();
+        assert!(errors.is_empty(), "{:?}", errors);
+        Program::parse(&mut tokens.into_iter().peekable())
+            .unwrap()
+            .statements
+    }
+
+    fn doc(statement: &Statement) -> Option<&str> {
+        match statement {
+            Statement::Declaration(declaration) => declaration.doc.as_deref(),
+            Statement::Function(function) => function.doc.as_deref(),
+            Statement::Class(class) => class.doc.as_deref(),
+            Statement::Export(declaration) => doc(declaration),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn doc_comments_attach_to_declarations() {
+        let statements = parse(
+            "
+            /// Adds numbers.
+            /// Second line.
+            fun add(a, b) { return a + b; }
+            /// A point.
+            class P {
+              /// A method.
+              m() {}
+            }
+            /// A variable.
+            var x = 1;
+            /// A constant.
+            const Y = 2;
+            /// An exported variable.
+            export var z;
+            /// An exported constant.
+            export const W = 3;
+            ",
+        );
+        let docs: Vec<_> = statements.iter().map(doc).collect();
+        assert_eq!(
+            docs,
+            [
+                Some("Adds numbers.\nSecond line."),
+                Some("A point."),
+                Some("A variable."),
+                Some("A constant."),
+                Some("An exported variable."),
+                Some("An exported constant."),
+            ]
+        );
+        match &statements[1] {
+            Statement::Class(class) => {
+                assert_eq!(class.methods[0].doc.as_deref(), Some("A method."))
+            }
+            statement => panic!("expected a class, found {:?}", statement),
+        }
+    }
+
+    #[test]
+    fn other_comments_are_not_docs() {
+        let statements = parse(
+            "
+            // a comment
+            var a;
+            //// not a doc
+            var b;
+            /* block */
+            var c;
+            /// a doc on a statement that can't have one
+            print 1;
+            var d;
+            ",
+        );
+        assert!(statements.iter().all(|statement| doc(statement).is_none()));
+    }
+}
@@ -1,7 +1,40 @@
-use crate::ast::BinaryOp;
+use crate::ast::{BinaryOp, Parameter, Position, Type};
 use crate::tokens::{Token, TokenType};
 use std::fmt::Display;
 use std::iter::Peekable;
+use std::rc::Rc;
+
+/// The position of the next token, which is the start of the node parsed
+/// from it.
+pub fn position<T>(tokens: &mut Peekable<T>) -> Position
+where
+    T: Iterator<Item = Token>,
+{
+    match tokens.peek() {
+        Some(token) => Position {
+            line: token.line,
+            column: token.utf16,
+        },
+        None => Position::default(),
+    }
+}
+
+/// Adds a parameter after the others, failing if it has no default value
+/// while one before it has. `position` is where the parameter starts.
+pub fn push_parameter(
+    params: &mut Vec<Parameter>,
+    param: Parameter,
+    position: Position,
+) -> Result<(), String> {
+    if param.default.is_none() && params.iter().any(|p| p.default.is_some()) {
+        return Err(format!(
+            "{} Parameter '{}' must have a default value since it follows one",
+            position, param.name
+        ));
+    }
+    params.push(param);
+    Ok(())
+}
 
 pub fn get_identifier<T>(tokens: &mut Peekable<T>) -> Result<String, String>
 where
@@ -12,11 +45,49 @@ where
             TokenType::IDENTIFIER(identifier) => Ok(identifier),
             _ => Err(format!(
                 "L{}:{} Expected IDENTIFIER, found {:?}",
-                token.line, token.offset, token.token_type
+                token.line, token.utf16, token.token_type
             )),
         },
-        None => Err(format!("Expected IDENTIFIER, found EOF")),
+        None => Err("Expected IDENTIFIER, found EOF".to_string()),
+    }
+}
+
+/// Parses an optional type annotation such as `: number` or `: string?`.
+pub fn annotation<T>(tokens: &mut Peekable<T>) -> Result<Option<Type>, String>
+where
+    T: Iterator<Item = Token>,
+{
+    if !match_next_token!(tokens, TokenType::COLON) {
+        return Ok(None);
     }
+    parse_type(tokens).map(Some)
+}
+
+/// Parses a type, which is a builtin type name, `any`, `nil` or the name of
+/// a class, followed by `?` when nil is also allowed.
+pub fn parse_type<T>(tokens: &mut Peekable<T>) -> Result<Type, String>
+where
+    T: Iterator<Item = Token>,
+{
+    let name = match tokens.next() {
+        Some(token) => match token.token_type {
+            TokenType::IDENTIFIER(identifier) => identifier,
+            TokenType::NIL => "nil".to_string(),
+            _ => {
+                return Err(format!(
+                    "L{}:{} Expected a type, found {:?}",
+                    token.line, token.utf16, token.token_type
+                ))
+            }
+        },
+        None => return Err("Expected a type, found EOF".to_string()),
+    };
+    let nullable = match_next_token!(tokens, TokenType::QUESTION);
+
+    Ok(Type {
+        name: Rc::new(name),
+        nullable,
+    })
 }
 
 pub fn consume<T, U>(
@@ -38,29 +109,40 @@ where
                 if let TokenType::IDENTIFIER(_) = token {
                     Ok(tokens.next().unwrap().token_type)
                 } else {
-                    Err(format!("L{}:{} {}", t.line, t.offset, message))
+                    Err(format!("L{}:{} {}", t.line, t.utf16, message))
                 }
             }
-            _ => Err(format!("L{}:{} {}", t.line, t.offset, message)),
+            _ => Err(format!("L{}:{} {}", t.line, t.utf16, message)),
         },
         None => Err(format!("{} found EOF", message)),
     }
 }
 
-pub fn map_binary_op(token: TokenType) -> BinaryOp {
+pub fn map_binary_op(token: TokenType) -> Result<BinaryOp, String> {
     use self::BinaryOp::*;
     use self::TokenType::*;
 
-    match token {
+    let op = match token {
         EQUAL_EQUAL => EqualEqual,
         BANG_EQUAL => NotEqual,
         LESS => Less,
         LESS_EQUAL => LessEqual,
         GREATER => Greater,
         GREATER_EQUAL => GreaterEqual,
-        PLUS => Plus,
-        HYPHEN => Minus,
-        ASTERICS => Multiply,
-        _ => Divide,
-    }
+        PLUS | PLUS_EQUAL | PLUS_PLUS => Plus,
+        HYPHEN | HYPHEN_EQUAL | HYPHEN_HYPHEN => Minus,
+        ASTERICS | ASTERICS_EQUAL => Multiply,
+        SLASH | SLASH_EQUAL => Divide,
+        TILDE_SLASH => IntegerDivide,
+        PERCENT => Modulo,
+        ASTERICS_ASTERICS => Power,
+        AMPERSAND => BitAnd,
+        PIPE => BitOr,
+        CARET => BitXor,
+        LESS_LESS => ShiftLeft,
+        GREATER_GREATER => ShiftRight,
+        token => return Err(format!("{:?} is not a binary operator", token)),
+    };
+
+    Ok(op)
 }
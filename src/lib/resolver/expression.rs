@@ -0,0 +1,178 @@
+use super::{resolve_function, ClassType, FunctionType, Resolvable, Resolver};
+use crate::ast::Expression::*;
+use crate::ast::{
+    Assignment, Binary, Call, Expression, Get, Index, Literal, Logical, Set, SetIndex, Super,
+    Ternary, Unary, Update,
+};
+use std::rc::Rc;
+
+impl Resolvable for Expression {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        match self {
+            Literal(literal) => literal.resolve(resolver),
+            Unary(unary) => unary.resolve(resolver),
+            Binary(binary) => binary.resolve(resolver),
+            Logical(logical) => logical.resolve(resolver),
+            Ternary(ternary) => ternary.resolve(resolver),
+            Grouping(expression) => expression.resolve(resolver),
+            Assignment(assignment) => assignment.resolve(resolver),
+            Update(update) => update.resolve(resolver),
+            Call(call) => call.resolve(resolver),
+            Lambda(function) => {
+                resolve_function(Rc::make_mut(function), resolver, FunctionType::Function)
+            }
+            List(items) => {
+                for item in items {
+                    item.resolve(resolver);
+                }
+            }
+            Interpolation(parts) => {
+                for part in parts {
+                    part.resolve(resolver);
+                }
+            }
+            Map(entries) => {
+                for (key, value) in entries {
+                    key.resolve(resolver);
+                    value.resolve(resolver);
+                }
+            }
+            Get(get) => get.resolve(resolver),
+            Set(set) => set.resolve(resolver),
+            Index(index) => index.resolve(resolver),
+            SetIndex(set_index) => set_index.resolve(resolver),
+            Super(sup) => sup.resolve(resolver),
+            Check(check) => check.value.resolve(resolver),
+        }
+    }
+}
+
+impl Resolvable for Literal {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        match self {
+            Literal::Variable(var) => {
+                resolver.position = var.position;
+                let in_initializer = resolver
+                    .scopes
+                    .last()
+                    .and_then(|scope| scope.get(&var.name))
+                    .is_some_and(|binding| !binding.defined);
+                if in_initializer {
+                    let message = format!(
+                        "Cannot read local variable '{}' in its own initializer",
+                        var.name
+                    );
+                    resolver.error(var.position, message);
+                }
+                resolver.resolve_local(var);
+            }
+            Literal::This(var) => {
+                resolver.position = var.position;
+                if resolver.class == ClassType::None {
+                    let message = "Cannot use 'this' outside of a class".to_string();
+                    resolver.error(var.position, message);
+                }
+                resolver.resolve_local(var);
+            }
+            Literal::Primary(_) => (),
+        }
+    }
+}
+
+impl Resolvable for Unary {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.expression.resolve(resolver);
+    }
+}
+
+impl Resolvable for Binary {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.left.resolve(resolver);
+        self.right.resolve(resolver);
+    }
+}
+
+impl Resolvable for Logical {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.left.resolve(resolver);
+        self.right.resolve(resolver);
+    }
+}
+
+impl Resolvable for Ternary {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.cond.resolve(resolver);
+        self.success.resolve(resolver);
+        self.failure.resolve(resolver);
+    }
+}
+
+impl Resolvable for Assignment {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.value.resolve(resolver);
+        resolver.resolve_local(&mut self.variable);
+        resolver.check_assignable(&self.variable);
+    }
+}
+
+impl Resolvable for Update {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.target.resolve(resolver);
+        self.value.resolve(resolver);
+        if let Expression::Literal(Literal::Variable(variable)) = &*self.target {
+            resolver.check_assignable(variable);
+        }
+    }
+}
+
+impl Resolvable for Call {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        resolver.position = self.position;
+        self.callee.resolve(resolver);
+        for argument in &mut self.arguments {
+            argument.resolve(resolver);
+        }
+    }
+}
+
+impl Resolvable for Get {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.object.resolve(resolver);
+    }
+}
+
+impl Resolvable for Set {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.object.resolve(resolver);
+        self.value.resolve(resolver);
+    }
+}
+
+impl Resolvable for Index {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.object.resolve(resolver);
+        self.index.resolve(resolver);
+    }
+}
+
+impl Resolvable for SetIndex {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.object.resolve(resolver);
+        self.index.resolve(resolver);
+        self.value.resolve(resolver);
+    }
+}
+
+impl Resolvable for Super {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        let message = match resolver.class {
+            ClassType::None => Some("Cannot use 'super' outside of a class"),
+            ClassType::Class => Some("Cannot use 'super' in a class with no superclass"),
+            ClassType::Subclass => None,
+        };
+        if let Some(message) = message {
+            resolver.error(self.variable.position, message.to_string());
+        }
+        resolver.resolve_local(&mut self.variable);
+    }
+}
@@ -0,0 +1,191 @@
+mod expression;
+mod statement;
+
+use crate::ast::{Function, Position, Program, Variable};
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::rc::Rc;
+
+#[derive(Clone, Copy, PartialEq)]
+enum FunctionType {
+    None,
+    Function,
+    Method,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ClassType {
+    None,
+    Class,
+    Subclass,
+}
+
+/// A name declared in a scope. `defined` is unset while the initializer of
+/// the name is being resolved.
+#[derive(Clone, Copy, PartialEq)]
+struct Binding {
+    defined: bool,
+    constant: bool,
+}
+
+/// Walks the syntax tree before it is run, binding every variable reference
+/// to the scope that declares it and reporting scoping errors.
+struct Resolver {
+    scopes: Vec<HashMap<Rc<String>, Binding>>,
+    /// The constants declared in the global scope so far.
+    constants: HashSet<Rc<String>>,
+    function: FunctionType,
+    class: ClassType,
+    loops: usize,
+    /// The position of the last node resolved that has one, where errors
+    /// about nodes without a position of their own are reported.
+    position: Position,
+    errors: Vec<String>,
+}
+
+trait Resolvable {
+    fn resolve(&mut self, resolver: &mut Resolver);
+}
+
+impl Program {
+    pub fn resolve(&mut self) -> Result<(), Vec<String>> {
+        let mut resolver = Resolver {
+            scopes: vec![],
+            constants: HashSet::new(),
+            function: FunctionType::None,
+            class: ClassType::None,
+            loops: 0,
+            position: Position::default(),
+            errors: vec![],
+        };
+
+        for statement in &mut self.statements {
+            statement.resolve(&mut resolver);
+        }
+
+        if !resolver.errors.is_empty() {
+            Err(resolver.errors)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Resolver {
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Reports an error at `position`, in the format of parser errors.
+    fn error(&mut self, position: Position, message: String) {
+        self.errors.push(format!("{} {}", position, message));
+    }
+
+    fn declare(&mut self, name: &Rc<String>) {
+        let binding = Binding {
+            defined: false,
+            constant: false,
+        };
+        if let Some(scope) = self.scopes.last_mut() {
+            if scope.insert(name.clone(), binding).is_some() {
+                let message = format!("Variable '{}' is already declared in this scope", name);
+                self.error(self.position, message);
+            }
+        } else if self.constants.contains(name) {
+            let message = format!("Cannot redeclare constant '{}'", name);
+            self.error(self.position, message);
+        }
+    }
+
+    fn define(&mut self, name: &Rc<String>) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope
+                .entry(name.clone())
+                .or_insert(Binding {
+                    defined: true,
+                    constant: false,
+                })
+                .defined = true;
+        }
+    }
+
+    /// Marks a name declared in the innermost scope as constant.
+    fn constant(&mut self, name: &Rc<String>) {
+        match self.scopes.last_mut() {
+            Some(scope) => {
+                if let Some(binding) = scope.get_mut(name) {
+                    binding.constant = true;
+                }
+            }
+            None => {
+                self.constants.insert(name.clone());
+            }
+        }
+    }
+
+    /// Reports an assignment to a constant. A global constant is only known
+    /// here once its declaration has been resolved, so an assignment that
+    /// precedes it is checked when the program runs.
+    fn check_assignable(&mut self, variable: &Variable) {
+        let constant = match variable.depth {
+            Some(depth) => self
+                .scopes
+                .iter()
+                .rev()
+                .nth(depth)
+                .and_then(|scope| scope.get(&variable.name))
+                .is_some_and(|binding| binding.constant),
+            None => self.constants.contains(&variable.name),
+        };
+        if constant {
+            let message = format!("Cannot assign to constant '{}'", variable.name);
+            self.error(variable.position, message);
+        }
+    }
+
+    fn check_in_loop(&mut self, keyword: &str, position: Position) {
+        if self.loops == 0 {
+            let message = format!("Cannot use '{}' outside of a loop", keyword);
+            self.error(position, message);
+        }
+    }
+
+    fn resolve_local(&mut self, variable: &mut Variable) {
+        variable.depth = self
+            .scopes
+            .iter()
+            .rev()
+            .position(|scope| scope.contains_key(&variable.name));
+    }
+}
+
+fn resolve_function(function: &mut Function, resolver: &mut Resolver, kind: FunctionType) {
+    let enclosing = mem::replace(&mut resolver.function, kind);
+    let loops = mem::replace(&mut resolver.loops, 0);
+
+    resolver.begin_scope();
+    for param in &mut function.params {
+        if let Some(default) = &mut param.default {
+            default.resolve(resolver);
+        }
+        resolver.position = function.position;
+        resolver.declare(&param.name);
+        resolver.define(&param.name);
+        if param.constant {
+            resolver.constant(&param.name);
+        }
+    }
+    if let Some(rest) = &function.rest {
+        resolver.declare(rest);
+        resolver.define(rest);
+    }
+    function.body.resolve(resolver);
+    resolver.end_scope();
+
+    resolver.function = enclosing;
+    resolver.loops = loops;
+}
@@ -0,0 +1,246 @@
+use super::{resolve_function, ClassType, FunctionType, Resolvable, Resolver};
+use crate::ast::Statement::*;
+use crate::ast::{
+    self, Assignments, Block, Class, Conditional, Declaration, Destructuring, ForIn, Function,
+    Import, ImportNames, Iteration, Literal, Match, Pattern, Statement, Try,
+};
+use std::mem;
+use std::rc::Rc;
+
+impl Resolvable for Statement {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        match self {
+            Expression(expression) => expression.resolve(resolver),
+            Print(expression) => expression.resolve(resolver),
+            Declaration(declaration) => declaration.resolve(resolver),
+            Destructuring(destructuring) => destructuring.resolve(resolver),
+            Assignments(assignments) => assignments.resolve(resolver),
+            Function(function) => function.resolve(resolver),
+            Class(class) => class.resolve(resolver),
+            Block(block) => block.resolve(resolver),
+            Conditional(conditional) => conditional.resolve(resolver),
+            Iteration(iteration) => iteration.resolve(resolver),
+            ForIn(for_in) => for_in.resolve(resolver),
+            Match(matching) => matching.resolve(resolver),
+            Try(try_statement) => try_statement.resolve(resolver),
+            Throw(expression) => expression.resolve(resolver),
+            Import(import) => import.resolve(resolver),
+            Export(declaration) => {
+                declaration.resolve(resolver);
+                if !resolver.scopes.is_empty() {
+                    let message = "Can only export top-level declarations".to_string();
+                    resolver.error(resolver.position, message);
+                }
+            }
+            Return(expression, position) => {
+                resolver.position = *position;
+                if resolver.function == FunctionType::None {
+                    let message = "Cannot return from top-level code".to_string();
+                    resolver.error(*position, message);
+                }
+                if let Some(expression) = expression {
+                    expression.resolve(resolver);
+                }
+            }
+            Yield(expression, position) => {
+                resolver.position = *position;
+                if resolver.function == FunctionType::None {
+                    let message = "Cannot yield from top-level code".to_string();
+                    resolver.error(*position, message);
+                }
+                if let Some(expression) = expression {
+                    expression.resolve(resolver);
+                }
+            }
+            Break(position) => resolver.check_in_loop("break", *position),
+            Continue(position) => resolver.check_in_loop("continue", *position),
+        }
+    }
+}
+
+impl Resolvable for Declaration {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        resolver.position = self.position;
+        resolver.declare(&self.name);
+        if let Some(value) = &mut self.value {
+            value.resolve(resolver);
+        }
+        resolver.define(&self.name);
+        if self.constant {
+            resolver.constant(&self.name);
+        }
+    }
+}
+
+impl Resolvable for Destructuring {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        resolver.position = self.position;
+        let names = self.target.names();
+        for name in &names {
+            resolver.declare(name);
+        }
+        self.value.resolve(resolver);
+        for name in names {
+            resolver.define(name);
+            if self.constant {
+                resolver.constant(name);
+            }
+        }
+    }
+}
+
+impl Resolvable for Assignments {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        for value in &mut self.values {
+            value.resolve(resolver);
+        }
+        for target in &mut self.targets {
+            match target {
+                ast::Expression::Literal(Literal::Variable(variable)) => {
+                    resolver.resolve_local(variable);
+                    resolver.check_assignable(variable);
+                }
+                target => target.resolve(resolver),
+            }
+        }
+    }
+}
+
+impl Resolvable for Function {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        resolver.position = self.position;
+        resolver.declare(&self.name);
+        resolver.define(&self.name);
+        resolve_function(self, resolver, FunctionType::Function);
+    }
+}
+
+impl Resolvable for Class {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        let enclosing = mem::replace(&mut resolver.class, ClassType::Class);
+
+        resolver.position = self.position;
+        resolver.declare(&self.name);
+        resolver.define(&self.name);
+
+        if let Some(superclass) = &mut self.superclass {
+            resolver.class = ClassType::Subclass;
+            superclass.resolve(resolver);
+        }
+
+        resolver.begin_scope();
+        if self.superclass.is_some() {
+            resolver.define(&Rc::new("super".to_string()));
+        }
+
+        resolver.begin_scope();
+        resolver.define(&Rc::new("this".to_string()));
+        for method in &mut self.methods {
+            resolve_function(Rc::make_mut(method), resolver, FunctionType::Method);
+        }
+        resolver.end_scope();
+
+        resolver.end_scope();
+        resolver.class = enclosing;
+    }
+}
+
+impl Resolvable for Block {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        resolver.begin_scope();
+        for statement in &mut self.statements {
+            statement.resolve(resolver);
+        }
+        resolver.end_scope();
+    }
+}
+
+impl Resolvable for Conditional {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.cond.resolve(resolver);
+        self.success.resolve(resolver);
+        if let Some(failure) = &mut self.failure {
+            failure.resolve(resolver);
+        }
+    }
+}
+
+impl Resolvable for Iteration {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.cond.resolve(resolver);
+        resolver.loops += 1;
+        self.body.resolve(resolver);
+        resolver.loops -= 1;
+        if let Some(increment) = &mut self.increment {
+            increment.resolve(resolver);
+        }
+    }
+}
+
+impl Resolvable for ForIn {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.iterable.resolve(resolver);
+
+        resolver.begin_scope();
+        resolver.declare(&self.name);
+        resolver.define(&self.name);
+        resolver.loops += 1;
+        self.body.resolve(resolver);
+        resolver.loops -= 1;
+        resolver.end_scope();
+    }
+}
+
+impl Resolvable for Match {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.value.resolve(resolver);
+
+        for arm in &mut self.arms {
+            resolver.begin_scope();
+            for pattern in &arm.patterns {
+                if let Pattern::Binding(name) = pattern {
+                    resolver.declare(name);
+                    resolver.define(name);
+                }
+            }
+            if let Some(guard) = &mut arm.guard {
+                guard.resolve(resolver);
+            }
+            arm.body.resolve(resolver);
+            resolver.end_scope();
+        }
+    }
+}
+
+impl Resolvable for Import {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        match &self.names {
+            ImportNames::Module(name) => {
+                resolver.declare(name);
+                resolver.define(name);
+            }
+            ImportNames::Exports(names) => {
+                for name in names {
+                    resolver.declare(name);
+                    resolver.define(name);
+                }
+            }
+        }
+    }
+}
+
+impl Resolvable for Try {
+    fn resolve(&mut self, resolver: &mut Resolver) {
+        self.body.resolve(resolver);
+        if let Some(catch) = &mut self.catch {
+            resolver.begin_scope();
+            resolver.declare(&catch.name);
+            resolver.define(&catch.name);
+            catch.body.resolve(resolver);
+            resolver.end_scope();
+        }
+        if let Some(finally) = &mut self.finally {
+            finally.resolve(resolver);
+        }
+    }
+}
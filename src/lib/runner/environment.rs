@@ -1,83 +1,192 @@
+use super::module::Modules;
+use super::{name_error, type_error, Exception};
 use crate::ast::Value;
+use crate::Typing;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-#[derive(Debug)]
+/// The scopes visible at some point of a program. Every module has its own
+/// global scope, while the cache of loaded modules and the way imports are
+/// type checked are shared by all of them.
+#[derive(Debug, Clone)]
 pub struct Environment {
     scope: Rc<RefCell<Scope>>,
+    globals: Rc<RefCell<Scope>>,
+    dir: Rc<PathBuf>,
+    modules: Rc<RefCell<Modules>>,
+    typing: Typing,
+    /// The `Error` class of the prelude, which caught runtime errors are
+    /// instances of even where user code declares its own `Error`.
+    error_class: Option<Rc<Value>>,
 }
 
 #[derive(Debug)]
 struct Scope {
-    data: HashMap<Rc<String>, Rc<Value>>,
+    data: HashMap<Rc<String>, Binding>,
     parent: Option<Rc<RefCell<Scope>>>,
 }
 
+#[derive(Debug)]
+struct Binding {
+    value: Rc<Value>,
+    constant: bool,
+}
+
 impl Environment {
-    pub fn new() -> Self {
+    /// Creates the environment of a program whose imports are resolved
+    /// relative to `dir`.
+    pub fn new(dir: PathBuf, typing: Typing) -> Self {
+        let scope = Rc::new(RefCell::new(Scope::from(None)));
+        Environment {
+            scope: scope.clone(),
+            globals: scope,
+            dir: Rc::new(dir),
+            modules: Rc::new(RefCell::new(HashMap::new())),
+            typing,
+            error_class: None,
+        }
+    }
+
+    /// Creates the environment of a module in `dir`, with its own globals but
+    /// sharing the module cache.
+    pub fn module(&self, dir: PathBuf) -> Self {
+        let scope = Rc::new(RefCell::new(Scope::from(None)));
         Environment {
-            scope: Rc::new(RefCell::new(Scope::from(None))),
+            scope: scope.clone(),
+            globals: scope,
+            dir: Rc::new(dir),
+            modules: self.modules.clone(),
+            typing: self.typing,
+            error_class: None,
         }
     }
 
     pub fn append(&self) -> Self {
         Environment {
             scope: Rc::new(RefCell::new(Scope::from(Some(self.scope.clone())))),
+            globals: self.globals.clone(),
+            dir: self.dir.clone(),
+            modules: self.modules.clone(),
+            typing: self.typing,
+            error_class: self.error_class.clone(),
         }
     }
 
-    pub fn declare(&mut self, key: Rc<String>, value: Rc<Value>) {
-        self.scope.borrow_mut().declare(key, value)
+    /// Whether both environments are the same scope, such as the closure of
+    /// a single evaluation of a function.
+    pub fn ptr_eq(&self, other: &Environment) -> bool {
+        Rc::ptr_eq(&self.scope, &other.scope)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn modules(&self) -> &RefCell<Modules> {
+        &self.modules
+    }
+
+    pub fn typing(&self) -> Typing {
+        self.typing
+    }
+
+    pub fn error_class(&self) -> Option<&Rc<Value>> {
+        self.error_class.as_ref()
     }
 
-    pub fn get(&self, key: &Rc<String>) -> Option<Rc<Value>> {
+    /// Records the `Error` class of the prelude. Environments appended after
+    /// this share it.
+    pub fn set_error_class(&mut self, class: Rc<Value>) {
+        self.error_class = Some(class);
+    }
+
+    /// Declares a binding in the current scope, replacing any variable of
+    /// the same name. A constant can't be redeclared.
+    pub fn declare(&mut self, key: Rc<String>, value: Rc<Value>) -> Result<(), Exception> {
+        self.scope.borrow_mut().declare(key, value, false)
+    }
+
+    /// Declares a binding that can't be assigned to.
+    pub fn declare_constant(&mut self, key: Rc<String>, value: Rc<Value>) -> Result<(), Exception> {
+        self.scope.borrow_mut().declare(key, value, true)
+    }
+
+    pub fn get_local(&self, key: &Rc<String>) -> Option<Rc<Value>> {
         self.scope.borrow().get(key)
     }
 
-    pub fn assign(&mut self, key: Rc<String>, value: Rc<Value>) -> Result<Rc<Value>, String> {
-        self.scope
-            .borrow_mut()
-            .assign(key.clone(), value)
-            .ok_or(format!("Variable '{}' not declared", key))
+    /// Looks up `key` in the scope `depth` levels above the current one, or
+    /// in the global scope when the resolver left the reference unresolved.
+    pub fn get_at(&self, depth: Option<usize>, key: &Rc<String>) -> Option<Rc<Value>> {
+        self.ancestor(depth)
+            .and_then(|scope| scope.borrow().get(key))
     }
-}
 
-impl Clone for Environment {
-    fn clone(&self) -> Self {
-        Environment {
-            scope: self.scope.clone(),
+    pub fn assign_at(
+        &mut self,
+        depth: Option<usize>,
+        key: Rc<String>,
+        value: Rc<Value>,
+    ) -> Result<Rc<Value>, Exception> {
+        match self.ancestor(depth) {
+            Some(scope) => scope.borrow_mut().assign(key, value),
+            None => Err(name_error(format!("Variable '{}' not declared", key))),
         }
     }
+
+    fn ancestor(&self, depth: Option<usize>) -> Option<Rc<RefCell<Scope>>> {
+        let depth = match depth {
+            Some(depth) => depth,
+            None => return Some(self.globals.clone()),
+        };
+
+        let mut scope = self.scope.clone();
+        for _ in 0..depth {
+            let parent = scope.borrow().parent.clone()?;
+            scope = parent;
+        }
+
+        Some(scope)
+    }
 }
 
 impl Scope {
     fn from(parent: Option<Rc<RefCell<Scope>>>) -> Self {
         Scope {
             data: HashMap::new(),
-            parent: parent,
+            parent,
         }
     }
 
-    fn declare(&mut self, key: Rc<String>, value: Rc<Value>) {
-        self.data.insert(key, value);
+    fn declare(
+        &mut self,
+        key: Rc<String>,
+        value: Rc<Value>,
+        constant: bool,
+    ) -> Result<(), Exception> {
+        if let Some(Binding { constant: true, .. }) = self.data.get(&key) {
+            return Err(type_error(format!("Cannot redeclare constant '{}'", key)));
+        }
+        self.data.insert(key, Binding { value, constant });
+        Ok(())
     }
 
     fn get(&self, key: &Rc<String>) -> Option<Rc<Value>> {
-        self.data.get(key).cloned().or_else(|| {
-            self.parent
-                .as_ref()
-                .and_then(|scope| scope.borrow().get(key))
-        })
-    }
-
-    fn assign(&mut self, key: Rc<String>, value: Rc<Value>) -> Option<Rc<Value>> {
-        if self.data.contains_key(&key) {
-            self.data.insert(key, value)
-        } else {
-            self.parent
-                .as_mut()
-                .and_then(|scope| scope.borrow_mut().assign(key, value))
+        self.data.get(key).map(|binding| binding.value.clone())
+    }
+
+    fn assign(&mut self, key: Rc<String>, value: Rc<Value>) -> Result<Rc<Value>, Exception> {
+        match self.data.get_mut(&key) {
+            Some(binding) if binding.constant => {
+                Err(type_error(format!("Cannot assign to constant '{}'", key)))
+            }
+            Some(binding) => {
+                binding.value = value.clone();
+                Ok(value)
+            }
+            None => Err(name_error(format!("Variable '{}' not declared", key))),
         }
     }
 }
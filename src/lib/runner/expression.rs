@@ -1,38 +1,80 @@
 use super::environment::Environment;
-use super::{Evaluable, Executable, RetErr};
+use super::generator::Generator;
+use super::{
+    arithmetic_error, index_error, name_error, type_error, Callable, Evaluable, Exception,
+    Executable, RetErr,
+};
 use crate::ast::Expression::*;
 use crate::ast::{
-    Assignment, Binary, Call, Expression, Function, Literal, Logical, Primary, Unary, Value,
+    Assignment, Binary, BinaryOp, Call, Check, Class, Expression, Function, Get, Index, Instance,
+    Key, Literal, Logical, Primary, Set, SetIndex, Super, Ternary, Type, Unary, Update, Value,
 };
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::rc::Rc;
 
 impl Evaluable for Expression {
-    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, String> {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
         match self {
             Literal(literal) => literal.evaluate(env),
             Unary(unary) => unary.evaluate(env),
             Binary(binary) => binary.evaluate(env),
             Logical(logical) => logical.evaluate(env),
+            Ternary(ternary) => ternary.evaluate(env),
             Grouping(expression) => expression.evaluate(env),
             Assignment(assignment) => assignment.evaluate(env),
+            Update(update) => update.evaluate(env),
             Call(call) => call.evaluate(env),
+            Lambda(function) => Ok(Rc::new(Value::Function(function.clone(), env.clone()))),
+            List(items) => {
+                let mut values = vec![];
+                for item in items {
+                    values.push(item.evaluate(env)?);
+                }
+                Ok(Rc::new(Value::list(values)))
+            }
+            Interpolation(parts) => {
+                let mut string = std::string::String::new();
+                for part in parts {
+                    string.push_str(&part.evaluate(env)?.to_string());
+                }
+                Ok(Rc::new(Value::Literal(Primary::String(string))))
+            }
+            Map(entries) => {
+                let mut map = HashMap::new();
+                for (key, value) in entries {
+                    let key = Key::new(&*key.evaluate(env)?)?;
+                    map.insert(key, value.evaluate(env)?);
+                }
+                Ok(Rc::new(Value::Map(Rc::new(RefCell::new(map)))))
+            }
+            Get(get) => get.evaluate(env),
+            Set(set) => set.evaluate(env),
+            Index(index) => index.evaluate(env),
+            SetIndex(set_index) => set_index.evaluate(env),
+            Super(sup) => sup.evaluate(env),
+            Check(check) => check.evaluate(env),
         }
     }
 }
 
 impl Evaluable for Literal {
-    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, String> {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
         match self {
-            Literal::Variable(name) => env
-                .get(name)
-                .ok_or(format!("{} not defined", name)),
+            Literal::Variable(var) => env
+                .get_at(var.depth, &var.name)
+                .ok_or_else(|| name_error(format!("{} not defined", var.name))),
+            Literal::This(var) => env
+                .get_at(var.depth, &var.name)
+                .ok_or_else(|| Exception::from("Cannot use 'this' outside of a class".to_string())),
             Literal::Primary(primary) => Ok(Rc::new(Value::Literal(primary.clone()))),
         }
     }
 }
 
 impl Evaluable for Unary {
-    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, String> {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
         use crate::ast::{Primary::*, UnaryOp::*};
 
         let value = self.expression.evaluate(env)?;
@@ -41,8 +83,26 @@ impl Evaluable for Unary {
             (Not, Value::Literal(Boolean(ref val))) => Boolean(!val),
             (Not, Value::Literal(Nil)) => Boolean(true),
             (Not, _) => Boolean(false),
+            (Minus, Value::Literal(Integer(ref integer))) => {
+                Integer(integer.checked_neg().ok_or_else(|| {
+                    arithmetic_error(format!("Integer overflow in -({})", integer))
+                })?)
+            }
             (Minus, Value::Literal(Number(ref number))) => Number(-number),
-            (Minus, _) => return Err(format!("Can't apply unary operator '-' to {}", value)),
+            (Minus, _) => {
+                return Err(type_error(format!(
+                    "Can't apply unary operator '-' to {}",
+                    value
+                )))
+            }
+            (BitNot, Value::Literal(Integer(ref integer))) => Integer(!integer),
+            (BitNot, Value::Literal(Number(ref number))) => Integer(!to_integer(*number, "~")?),
+            (BitNot, _) => {
+                return Err(type_error(format!(
+                    "Can't apply unary operator '~' to {}",
+                    value
+                )))
+            }
         };
 
         Ok(Rc::new(Value::Literal(result)))
@@ -50,43 +110,114 @@ impl Evaluable for Unary {
 }
 
 impl Evaluable for Binary {
-    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, String> {
-        use self::Value::Literal;
-        use crate::ast::{BinaryOp::*, Primary::*};
-
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
         let left = self.left.evaluate(env)?;
         let right = self.right.evaluate(env)?;
 
-        let result = match (&*left, &self.op, &*right) {
-            (l, EqualEqual, r) => Boolean(l == r),
-            (l, NotEqual, r) => Boolean(l != r),
-            (Literal(Number(l)), Less, Literal(Number(r))) => Boolean(l < r),
-            (Literal(Number(l)), LessEqual, Literal(Number(r))) => Boolean(l <= r),
-            (Literal(Number(l)), Greater, Literal(Number(r))) => Boolean(l > r),
-            (Literal(Number(l)), GreaterEqual, Literal(Number(r))) => Boolean(l >= r),
-            (Literal(Number(l)), Divide, Literal(Number(r))) => Number(l / r),
-            (Literal(Number(l)), Minus, Literal(Number(r))) => Number(l - r),
-            (Literal(Number(l)), Multiply, Literal(Number(r))) => Number(l * r),
-            (Literal(Number(l)), Plus, Literal(Number(r))) => Number(l + r),
-            (Literal(String(l)), Plus, Literal(String(r))) => {
-                let mut s = l.clone();
-                s.push_str(&r);
-                String(s)
-            }
-            (l, op, r) => {
-                return Err(format!(
-                    "'{:?}' operator is not defined for {} and {}",
-                    op, l, r
-                ))
-            }
-        };
-
-        Ok(Rc::new(Literal(result)))
+        Ok(Rc::new(Value::Literal(binary(&left, &self.op, &right)?)))
     }
 }
 
+/// Applies a binary operator other than a logical one to two values.
+fn binary(left: &Value, op: &BinaryOp, right: &Value) -> Result<Primary, Exception> {
+    use self::Value::Literal;
+    use crate::ast::{BinaryOp::*, Primary::*};
+
+    let result = match (left, op, right) {
+        (l, EqualEqual, r) => Boolean(l == r),
+        (l, NotEqual, r) => Boolean(l != r),
+        (Literal(Integer(l)), op, Literal(Integer(r))) => integer_binary(*l, op, *r)?,
+        (Literal(Integer(l)), op, Literal(Number(r))) => float_binary(*l as f64, op, *r)?,
+        (Literal(Number(l)), op, Literal(Integer(r))) => float_binary(*l, op, *r as f64)?,
+        (Literal(Number(l)), op, Literal(Number(r))) => float_binary(*l, op, *r)?,
+        (Literal(String(l)), Plus, Literal(String(r))) => {
+            let mut s = l.clone();
+            s.push_str(r);
+            String(s)
+        }
+        (l, op, r) => {
+            return Err(type_error(format!(
+                "'{}' operator is not defined for {} and {}",
+                op, l, r
+            )))
+        }
+    };
+
+    Ok(result)
+}
+
+/// Applies a binary operator to two integers. Arithmetic that overflows an
+/// `i64` is an error rather than wrapping around, `/` always divides as
+/// floats, and a negative exponent gives a float.
+fn integer_binary(l: i64, op: &BinaryOp, r: i64) -> Result<Primary, Exception> {
+    use crate::ast::{BinaryOp::*, Primary::*};
+
+    let overflow = || arithmetic_error(format!("Integer overflow in {} {} {}", l, op, r));
+    let zero_division = || arithmetic_error(format!("Division by zero in {} {} {}", l, op, r));
+
+    let result = match op {
+        Less => Boolean(l < r),
+        LessEqual => Boolean(l <= r),
+        Greater => Boolean(l > r),
+        GreaterEqual => Boolean(l >= r),
+        Plus => Integer(l.checked_add(r).ok_or_else(overflow)?),
+        Minus => Integer(l.checked_sub(r).ok_or_else(overflow)?),
+        Multiply => Integer(l.checked_mul(r).ok_or_else(overflow)?),
+        Divide => Number(l as f64 / r as f64),
+        IntegerDivide if r == 0 => return Err(zero_division()),
+        IntegerDivide => Integer(l.checked_div(r).ok_or_else(overflow)?),
+        Modulo if r == 0 => return Err(zero_division()),
+        Modulo => Integer(l.checked_rem(r).ok_or_else(overflow)?),
+        Power if r < 0 => Number((l as f64).powf(r as f64)),
+        Power => Integer(
+            u32::try_from(r)
+                .ok()
+                .and_then(|r| l.checked_pow(r))
+                .ok_or_else(overflow)?,
+        ),
+        BitAnd => Integer(l & r),
+        BitOr => Integer(l | r),
+        BitXor => Integer(l ^ r),
+        ShiftLeft => Integer(l << shift_amount(r, op)?),
+        ShiftRight => Integer(l >> shift_amount(r, op)?),
+        EqualEqual | NotEqual => Boolean((l == r) == (*op == EqualEqual)),
+    };
+
+    Ok(result)
+}
+
+/// Applies a binary operator to two floats, or to an integer promoted to a
+/// float. Bitwise operators only accept floats with an integral value.
+fn float_binary(l: f64, op: &BinaryOp, r: f64) -> Result<Primary, Exception> {
+    use crate::ast::{BinaryOp::*, Primary::*};
+
+    let result = match op {
+        Less => Boolean(l < r),
+        LessEqual => Boolean(l <= r),
+        Greater => Boolean(l > r),
+        GreaterEqual => Boolean(l >= r),
+        Plus => Number(l + r),
+        Minus => Number(l - r),
+        Multiply => Number(l * r),
+        Divide => Number(l / r),
+        IntegerDivide => Number((l / r).trunc()),
+        Modulo => Number(l % r),
+        Power => Number(l.powf(r)),
+        BitAnd | BitOr | BitXor | ShiftLeft | ShiftRight => {
+            return integer_binary(
+                to_integer(l, &op.to_string())?,
+                op,
+                to_integer(r, &op.to_string())?,
+            )
+        }
+        EqualEqual | NotEqual => Boolean((l == r) == (*op == EqualEqual)),
+    };
+
+    Ok(result)
+}
+
 impl Evaluable for Logical {
-    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, String> {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
         use crate::ast::LogicalOp;
 
         let left = self.left.evaluate(env)?;
@@ -101,34 +232,462 @@ impl Evaluable for Logical {
     }
 }
 
+impl Evaluable for Ternary {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
+        if self.cond.evaluate(env)?.is_truthy() {
+            self.success.evaluate(env)
+        } else {
+            self.failure.evaluate(env)
+        }
+    }
+}
+
 impl Evaluable for Assignment {
-    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, String> {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
         let value = self.value.evaluate(env)?;
-        env.assign(self.variable.clone(), value)
+        env.assign_at(self.variable.depth, self.variable.name.clone(), value)
     }
 }
 
 impl Evaluable for Call {
-    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, String> {
-        let result = self.callee.evaluate(env)?;
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
+        let callee = self.callee.evaluate(env)?;
+
+        let mut arguments = vec![];
+        for argument in &self.arguments {
+            arguments.push(argument.evaluate(env)?);
+        }
+
+        callee.call(arguments)
+    }
+}
+
+impl Callable for Rc<Value> {
+    fn call(&self, arguments: Vec<Rc<Value>>) -> Result<Rc<Value>, Exception> {
+        match &**self {
+            Value::Function(func, func_env) => {
+                check_arity(&func.name, func.arity(), arguments.len()).map_err(type_error)?;
+
+                let mut func_env = func_env.append();
+                let mut arguments = arguments.into_iter();
+
+                for param in &func.params {
+                    let value = match (arguments.next(), &param.default) {
+                        (Some(value), _) => value,
+                        (None, Some(default)) => default.evaluate(&mut func_env)?,
+                        (None, None) => Rc::new(Value::Literal(Primary::Nil)),
+                    };
+                    if param.constant {
+                        func_env.declare_constant(param.name.clone(), value)?;
+                    } else {
+                        func_env.declare(param.name.clone(), value)?;
+                    }
+                }
+
+                if let Some(rest) = &func.rest {
+                    let rest_value = Value::List(Rc::new(RefCell::new(arguments.collect())));
+                    func_env.declare(rest.clone(), Rc::new(rest_value))?;
+                }
+
+                if func.generator {
+                    let generator = Generator::new(func.clone(), func_env);
+                    return Ok(Rc::new(Value::Generator(Rc::new(generator))));
+                }
+
+                func.evaluate(&mut func_env)
+            }
+            Value::Native(native) => {
+                let (min, max) = native.arity;
+                check_arity(native.name, (min, Some(max)), arguments.len()).map_err(type_error)?;
+                Ok((native.function)(arguments)?)
+            }
+            Value::Class(class, class_env) => {
+                let instance = Rc::new(Value::Instance(Rc::new(Instance {
+                    class: class.clone(),
+                    class_env: class_env.clone(),
+                    fields: RefCell::new(HashMap::new()),
+                })));
+
+                // the arity of `init` is checked here to report it as the class's
+                let init = find_method(class, class_env, "init");
+                let arity = init.as_ref().map_or((0, Some(0)), |(init, _)| init.arity());
+                check_arity(&class.name, arity, arguments.len()).map_err(type_error)?;
+                if let Some((init, class_env)) = init {
+                    bind(&init, &class_env, instance.clone())?.call(arguments)?;
+                }
+
+                Ok(instance)
+            }
+            Value::Method(receiver, name) => {
+                check_arity(name, (0, Some(0)), arguments.len()).map_err(type_error)?;
+                match (&**receiver, *name) {
+                    (Value::Generator(generator), "next") => generator.resume(),
+                    _ => Err(name_error(format!("Undefined method '{}'", name))),
+                }
+            }
+            value => Err(type_error(format!("{} is not callable", value))),
+        }
+    }
+}
+
+impl Evaluable for Get {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
+        let object = self.object.evaluate(env)?;
+        get_property(&object, &self.name)
+    }
+}
+
+impl Evaluable for Set {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
+        let object = self.object.evaluate(env)?;
+        let value = self.value.evaluate(env)?;
+        set_property(&object, &self.name, value)
+    }
+}
+
+impl Evaluable for Index {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
+        let object = self.object.evaluate(env)?;
+        let index = self.index.evaluate(env)?;
+        get_index(&object, &index)
+    }
+}
+
+impl Evaluable for SetIndex {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
+        let object = self.object.evaluate(env)?;
+        let index = self.index.evaluate(env)?;
+        let value = self.value.evaluate(env)?;
+        set_index(&object, &index, value)
+    }
+}
+
+impl Evaluable for Update {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
+        let apply = |current: &Value, env: &mut Environment| -> Result<Rc<Value>, Exception> {
+            let value = self.value.evaluate(env)?;
+            Ok(Rc::new(Value::Literal(binary(current, &self.op, &value)?)))
+        };
+
+        let (old, new) = match &*self.target {
+            Literal(Literal::Variable(var)) => {
+                let old = env
+                    .get_at(var.depth, &var.name)
+                    .ok_or_else(|| name_error(format!("{} not defined", var.name)))?;
+                let new = apply(&old, env)?;
+                (old, env.assign_at(var.depth, var.name.clone(), new)?)
+            }
+            Get(get) => {
+                let object = get.object.evaluate(env)?;
+                let old = get_property(&object, &get.name)?;
+                let new = apply(&old, env)?;
+                (old, set_property(&object, &get.name, new)?)
+            }
+            Index(index) => {
+                let object = index.object.evaluate(env)?;
+                let key = index.index.evaluate(env)?;
+                let old = get_index(&object, &key)?;
+                let new = apply(&old, env)?;
+                (old, set_index(&object, &key, new)?)
+            }
+            _ => return Err(Exception::from("Invalid assignment target".to_string())),
+        };
+
+        Ok(if self.postfix { old } else { new })
+    }
+}
+
+/// Assigns a value to a variable, property or index target.
+pub(super) fn assign(
+    target: &Expression,
+    value: Rc<Value>,
+    env: &mut Environment,
+) -> Result<Rc<Value>, Exception> {
+    match target {
+        Literal(Literal::Variable(var)) => env.assign_at(var.depth, var.name.clone(), value),
+        Get(get) => {
+            let object = get.object.evaluate(env)?;
+            set_property(&object, &get.name, value)
+        }
+        Index(index) => {
+            let object = index.object.evaluate(env)?;
+            let key = index.index.evaluate(env)?;
+            set_index(&object, &key, value)
+        }
+        _ => Err(Exception::from("Invalid assignment target".to_string())),
+    }
+}
+
+impl Evaluable for Super {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
+        let depth = self.variable.depth;
+        let superclass = env.get_at(depth, &self.variable.name).ok_or_else(|| {
+            Exception::from("Cannot use 'super' outside of a subclass".to_string())
+        })?;
+        // `this` is bound in the scope just inside the one holding `super`
+        let instance = env
+            .get_at(
+                depth.and_then(|depth| depth.checked_sub(1)),
+                &Rc::new("this".to_string()),
+            )
+            .ok_or_else(|| Exception::from("Cannot use 'super' outside of a method".to_string()))?;
 
-        let (func, func_env) = match *result {
-            Value::Function(ref func, ref func_env) => (func, func_env),
-            ref value => return Err(format!("{} is not callable", value)),
+        let method = match &*superclass {
+            Value::Class(class, class_env) => find_method(class, class_env, &self.method),
+            _ => None,
         };
+        match method {
+            Some((method, class_env)) => bind(&method, &class_env, instance),
+            None => Err(name_error(format!(
+                "Undefined superclass method '{}'",
+                self.method
+            ))),
+        }
+    }
+}
+
+pub(super) fn get_property(object: &Rc<Value>, name: &Rc<String>) -> Result<Rc<Value>, Exception> {
+    let instance = match &**object {
+        Value::Instance(instance) => instance,
+        Value::Module(module) => {
+            return module.get(name).ok_or_else(|| {
+                name_error(format!(
+                    "Module '{}' has no export '{}'",
+                    module.path.display(),
+                    name
+                ))
+            })
+        }
+        Value::Generator(generator) => {
+            return match name.as_str() {
+                "next" => Ok(Rc::new(Value::Method(object.clone(), "next"))),
+                "done" => Ok(Rc::new(Value::Literal(Primary::Boolean(
+                    generator.is_done(),
+                )))),
+                _ => Err(name_error(format!("Undefined property '{}'", name))),
+            }
+        }
+        value => {
+            return Err(type_error(format!(
+                "Only instances have properties, found {}",
+                value
+            )))
+        }
+    };
+
+    if let Some(value) = instance.fields.borrow().get(name) {
+        return Ok(value.clone());
+    }
+
+    match find_method(&instance.class, &instance.class_env, name) {
+        Some((method, class_env)) => bind(&method, &class_env, object.clone()),
+        None => Err(name_error(format!("Undefined property '{}'", name))),
+    }
+}
+
+fn set_property(
+    object: &Value,
+    name: &Rc<String>,
+    value: Rc<Value>,
+) -> Result<Rc<Value>, Exception> {
+    let instance = match object {
+        Value::Instance(instance) => instance,
+        value => {
+            return Err(type_error(format!(
+                "Only instances have fields, found {}",
+                value
+            )))
+        }
+    };
+
+    instance
+        .fields
+        .borrow_mut()
+        .insert(name.clone(), value.clone());
+
+    Ok(value)
+}
+
+fn get_index(object: &Value, index: &Value) -> Result<Rc<Value>, Exception> {
+    match object {
+        Value::List(list) => {
+            let list = list.borrow();
+            let index = list_index(index, list.len()).map_err(index_error)?;
+            Ok(list[index].clone())
+        }
+        Value::Map(map) => {
+            let key = Key::new(index).map_err(type_error)?;
+            map.borrow()
+                .get(&key)
+                .cloned()
+                .ok_or_else(|| index_error(format!("Key '{}' not found in map", index)))
+        }
+        value => Err(type_error(format!("{} is not indexable", value))),
+    }
+}
+
+fn set_index(object: &Value, index: &Value, value: Rc<Value>) -> Result<Rc<Value>, Exception> {
+    match object {
+        Value::List(list) => {
+            let mut list = list.borrow_mut();
+            let index = list_index(index, list.len()).map_err(index_error)?;
+            list[index] = value.clone();
+            Ok(value)
+        }
+        Value::Map(map) => {
+            let key = Key::new(index).map_err(type_error)?;
+            map.borrow_mut().insert(key, value.clone());
+            Ok(value)
+        }
+        value => Err(type_error(format!(
+            "{} does not support index assignment",
+            value
+        ))),
+    }
+}
+
+/// Converts a float operand of a bitwise operator into an integer.
+fn to_integer(number: f64, op: &str) -> Result<i64, Exception> {
+    if number.fract() == 0.0 && number.abs() < i64::MAX as f64 {
+        Ok(number as i64)
+    } else {
+        Err(type_error(format!(
+            "Operator '{}' expects integer operands, found {}",
+            op, number
+        )))
+    }
+}
+
+fn shift_amount(amount: i64, op: &BinaryOp) -> Result<i64, Exception> {
+    match amount {
+        amount @ 0..=63 => Ok(amount),
+        amount => Err(arithmetic_error(format!(
+            "Shift amount for '{}' must be between 0 and 63, found {}",
+            op, amount
+        ))),
+    }
+}
+
+/// Converts a Lox value into an index into a list of length `len`.
+pub fn list_index(index: &Value, len: usize) -> Result<usize, String> {
+    match index {
+        Value::Literal(Primary::Integer(integer)) => {
+            if *integer >= 0 && (*integer as usize) < len {
+                Ok(*integer as usize)
+            } else {
+                Err(format!(
+                    "Index {} out of bounds for list of length {}",
+                    integer, len
+                ))
+            }
+        }
+        value => Err(format!("List index must be an integer, found {}", value)),
+    }
+}
+
+fn check_arity(name: &str, arity: (usize, Option<usize>), count: usize) -> Result<(), String> {
+    let (expected, last) = match arity {
+        (min, Some(max)) if min <= count && count <= max => return Ok(()),
+        (min, None) if min <= count => return Ok(()),
+        (min, Some(max)) if min == max => (format!("{}", min), min),
+        (min, Some(max)) => (format!("between {} and {}", min, max), max),
+        (min, None) => (format!("at least {}", min), min),
+    };
+    let noun = if last == 1 { "argument" } else { "arguments" };
+
+    Err(format!(
+        "{} expects {} {} but got {}",
+        name, expected, noun, count
+    ))
+}
 
-        let mut func_env = func_env.append();
+impl Evaluable for Check {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
+        let value = self.value.evaluate(env)?;
+        check_type(value, &self.annotation, &self.context)
+    }
+}
 
-        for (key, val) in func.params.iter().zip(&self.arguments) {
-            func_env.declare(key.clone(), val.evaluate(env)?);
+/// Fails unless `value` has the annotated type. `context` describes what the
+/// value is for.
+pub(super) fn check_type(
+    value: Rc<Value>,
+    annotation: &Type,
+    context: &str,
+) -> Result<Rc<Value>, Exception> {
+    if has_type(&value, annotation) {
+        return Ok(value);
+    }
+
+    Err(type_error(format!(
+        "{} expects {}, found {} of type {}",
+        context,
+        annotation,
+        value,
+        value.type_name()
+    )))
+}
+
+/// Tests whether `value` has the annotated type. A class name matches
+/// instances of that class or of its subclasses.
+fn has_type(value: &Value, annotation: &Type) -> bool {
+    match (annotation.name.as_str(), value) {
+        (_, Value::Literal(Primary::Nil)) if annotation.nullable => true,
+        ("any", _) => true,
+        ("number", value) => matches!(value.type_name(), "int" | "float"),
+        (name, value) if crate::ast::TYPE_NAMES.contains(&name) => value.type_name() == name,
+        (name, Value::Instance(instance)) => {
+            instance_of(&instance.class, &instance.class_env, name)
         }
+        _ => false,
+    }
+}
+
+fn instance_of(class: &Class, class_env: &Environment, name: &str) -> bool {
+    class.name.as_str() == name
+        || superclass(class_env).is_some_and(|(superclass, superclass_env)| {
+            instance_of(&superclass, &superclass_env, name)
+        })
+}
 
-        func.evaluate(&mut func_env)
+/// Looks up a method on a class, walking up the superclass chain. Returns
+/// the method along with the environment of the class that defines it.
+pub(super) fn find_method(
+    class: &Class,
+    class_env: &Environment,
+    name: &str,
+) -> Option<(Rc<Function>, Environment)> {
+    match class.find_method(name) {
+        Some(method) => Some((method.clone(), class_env.clone())),
+        None => superclass(class_env).and_then(|(superclass, superclass_env)| {
+            find_method(&superclass, &superclass_env, name)
+        }),
     }
 }
 
+/// The superclass of a class, which is bound as `super` in the environment
+/// the class was declared in. It is checked to be a class when declared.
+fn superclass(class_env: &Environment) -> Option<(Rc<Class>, Environment)> {
+    match &*class_env.get_local(&Rc::new("super".to_string()))? {
+        Value::Class(superclass, superclass_env) => {
+            Some((superclass.clone(), superclass_env.clone()))
+        }
+        _ => None,
+    }
+}
+
+fn bind(
+    method: &Rc<Function>,
+    class_env: &Environment,
+    instance: Rc<Value>,
+) -> Result<Rc<Value>, Exception> {
+    let mut method_env = class_env.append();
+    method_env.declare(Rc::new("this".to_string()), instance)?;
+    Ok(Rc::new(Value::Function(method.clone(), method_env)))
+}
+
 impl Evaluable for Function {
-    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, String> {
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception> {
         let value = match self.body.execute(env) {
             Err(RetErr::Return(val)) => val,
             Err(RetErr::Error(err)) => return Err(err),
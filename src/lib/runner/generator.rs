@@ -0,0 +1,360 @@
+use super::environment::Environment;
+use super::iterator::Iter;
+use super::{Evaluable, Exception, Executable, RetErr};
+use crate::ast::{
+    Block, Conditional, ForIn, Function, Iteration, Match, Primary, Statement, Try, Value,
+};
+use std::cell::RefCell;
+use std::fmt;
+use std::mem;
+use std::rc::Rc;
+
+/// The suspended call of a function containing `yield`. Each call to `next`
+/// runs the body until the following `yield`, and the point where it stopped
+/// is kept as a `Cursor` so that the next call can pick up from there.
+pub struct Generator {
+    function: Rc<Function>,
+    state: RefCell<State>,
+}
+
+enum State {
+    Suspended(Environment, Cursor),
+    Running,
+    Done,
+}
+
+/// Where a statement stopped at a `yield`, along with the environments of
+/// the scopes it had entered.
+enum Cursor {
+    /// The statement hasn't started running.
+    Start,
+    /// Suspended at a `yield` statement.
+    Yield,
+    /// Suspended in the statement at the index of a block.
+    Block(Environment, usize, Box<Cursor>),
+    /// Suspended in the branch taken by a conditional.
+    Branch(bool, Box<Cursor>),
+    /// Suspended in the body of a loop.
+    Loop(Box<Cursor>),
+    /// Suspended in the body of a `for-in` loop, with the rest of the items.
+    ForIn(Iter, Environment, Box<Cursor>),
+    /// Suspended in the body of the match arm at the index.
+    Arm(usize, Environment, Box<Cursor>),
+    /// Suspended in the body of a `try` statement.
+    Try(Box<Cursor>),
+    /// Suspended in a `catch` clause.
+    Catch(Environment, Box<Cursor>),
+    /// Suspended in a `finally` clause, along with how the body was left.
+    Finally(Result<(), RetErr>, Box<Cursor>),
+}
+
+enum Step {
+    Yielded(Rc<Value>, Cursor),
+    Finished,
+}
+
+/// Runs a statement of a generator from the cursor until it either finishes
+/// or reaches a `yield`. Statements that can't hold a `yield` are executed.
+trait Resumable {
+    fn resume(&self, cursor: Cursor, env: &mut Environment) -> Result<Step, RetErr>;
+}
+
+impl Generator {
+    /// Creates the generator of a call to `function`, with the arguments
+    /// already declared in `env`.
+    pub fn new(function: Rc<Function>, env: Environment) -> Self {
+        Generator {
+            function,
+            state: RefCell::new(State::Suspended(env, Cursor::Start)),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.function.name
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(*self.state.borrow(), State::Done)
+    }
+
+    /// Runs the function until its next `yield` and returns the yielded
+    /// value. Once the function returns, the generator is done and this
+    /// returns the function's return value, and then nil.
+    pub fn resume(&self) -> Result<Rc<Value>, Exception> {
+        let state = mem::replace(&mut *self.state.borrow_mut(), State::Running);
+        let (mut env, cursor) = match state {
+            State::Suspended(env, cursor) => (env, cursor),
+            State::Running => {
+                return Err(Exception::from(format!(
+                    "Generator {} is already running",
+                    self.name()
+                )))
+            }
+            State::Done => {
+                *self.state.borrow_mut() = State::Done;
+                return Ok(Rc::new(Value::Literal(Primary::Nil)));
+            }
+        };
+
+        let (state, result) = match self.function.body.resume(cursor, &mut env) {
+            Ok(Step::Yielded(value, cursor)) => (State::Suspended(env, cursor), Ok(value)),
+            Err(RetErr::Return(value)) => (State::Done, Ok(value)),
+            Err(RetErr::Error(exception)) => (State::Done, Err(exception)),
+            _ => (State::Done, Ok(Rc::new(Value::Literal(Primary::Nil)))),
+        };
+        *self.state.borrow_mut() = state;
+
+        result
+    }
+}
+
+impl fmt::Debug for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Generator")
+            .field("function", &self.function.name)
+            .field("done", &self.is_done())
+            .finish()
+    }
+}
+
+impl Resumable for Statement {
+    fn resume(&self, cursor: Cursor, env: &mut Environment) -> Result<Step, RetErr> {
+        match self {
+            Statement::Block(block) => block.resume(cursor, env),
+            Statement::Conditional(conditional) => conditional.resume(cursor, env),
+            Statement::Iteration(iteration) => iteration.resume(cursor, env),
+            Statement::ForIn(for_in) => for_in.resume(cursor, env),
+            Statement::Match(matching) => matching.resume(cursor, env),
+            Statement::Try(try_statement) => try_statement.resume(cursor, env),
+            Statement::Yield(expression, _) => {
+                if let Cursor::Yield = cursor {
+                    return Ok(Step::Finished);
+                }
+                let value = match expression {
+                    Some(expression) => expression.evaluate(env).map_err(RetErr::Error)?,
+                    None => Rc::new(Value::Literal(Primary::Nil)),
+                };
+                Ok(Step::Yielded(value, Cursor::Yield))
+            }
+            statement => statement.execute(env).map(|()| Step::Finished),
+        }
+    }
+}
+
+impl Resumable for Block {
+    fn resume(&self, cursor: Cursor, env: &mut Environment) -> Result<Step, RetErr> {
+        let (mut block_env, start, mut cursor) = match cursor {
+            Cursor::Block(block_env, index, cursor) => (block_env, index, *cursor),
+            _ => (env.append(), 0, Cursor::Start),
+        };
+
+        for (index, statement) in self.statements.iter().enumerate().skip(start) {
+            let cursor = mem::replace(&mut cursor, Cursor::Start);
+            if let Step::Yielded(value, cursor) = statement.resume(cursor, &mut block_env)? {
+                let cursor = Cursor::Block(block_env, index, Box::new(cursor));
+                return Ok(Step::Yielded(value, cursor));
+            }
+        }
+
+        Ok(Step::Finished)
+    }
+}
+
+impl Resumable for Conditional {
+    fn resume(&self, cursor: Cursor, env: &mut Environment) -> Result<Step, RetErr> {
+        let (branch, cursor) = match cursor {
+            Cursor::Branch(branch, cursor) => (branch, *cursor),
+            _ => {
+                let cond = self.cond.evaluate(env).map_err(RetErr::Error)?;
+                (cond.is_truthy(), Cursor::Start)
+            }
+        };
+
+        let statement = if branch {
+            Some(&self.success)
+        } else {
+            self.failure.as_ref()
+        };
+
+        match statement {
+            Some(statement) => match statement.resume(cursor, env)? {
+                Step::Yielded(value, cursor) => Ok(Step::Yielded(
+                    value,
+                    Cursor::Branch(branch, Box::new(cursor)),
+                )),
+                Step::Finished => Ok(Step::Finished),
+            },
+            None => Ok(Step::Finished),
+        }
+    }
+}
+
+impl Resumable for Iteration {
+    fn resume(&self, cursor: Cursor, env: &mut Environment) -> Result<Step, RetErr> {
+        let mut cursor = match cursor {
+            Cursor::Loop(cursor) => Some(*cursor),
+            _ => None,
+        };
+
+        loop {
+            let cursor = match cursor.take() {
+                Some(cursor) => cursor,
+                None if self.cond.evaluate(env).map_err(RetErr::Error)?.is_truthy() => {
+                    Cursor::Start
+                }
+                None => break,
+            };
+
+            match self.body.resume(cursor, env) {
+                Ok(Step::Yielded(value, cursor)) => {
+                    return Ok(Step::Yielded(value, Cursor::Loop(Box::new(cursor))))
+                }
+                Err(RetErr::Break) => break,
+                Err(RetErr::Continue) | Ok(Step::Finished) => (),
+                Err(err) => return Err(err),
+            }
+
+            if let Some(increment) = &self.increment {
+                increment.evaluate(env).map_err(RetErr::Error)?;
+            }
+        }
+
+        Ok(Step::Finished)
+    }
+}
+
+impl Resumable for ForIn {
+    fn resume(&self, cursor: Cursor, env: &mut Environment) -> Result<Step, RetErr> {
+        let (mut items, mut current) = match cursor {
+            Cursor::ForIn(items, loop_env, cursor) => (items, Some((loop_env, *cursor))),
+            _ => {
+                let iterable = self.iterable.evaluate(env).map_err(RetErr::Error)?;
+                (Iter::new(&iterable).map_err(RetErr::Error)?, None)
+            }
+        };
+
+        loop {
+            let (mut loop_env, cursor) = match current.take() {
+                Some(current) => current,
+                None => match items.next().map_err(RetErr::Error)? {
+                    Some(item) => {
+                        let mut loop_env = env.append();
+                        loop_env
+                            .declare(self.name.clone(), item)
+                            .map_err(RetErr::Error)?;
+                        (loop_env, Cursor::Start)
+                    }
+                    None => break,
+                },
+            };
+
+            match self.body.resume(cursor, &mut loop_env) {
+                Ok(Step::Yielded(value, cursor)) => {
+                    let cursor = Cursor::ForIn(items, loop_env, Box::new(cursor));
+                    return Ok(Step::Yielded(value, cursor));
+                }
+                Err(RetErr::Break) => break,
+                Err(RetErr::Continue) | Ok(Step::Finished) => (),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(Step::Finished)
+    }
+}
+
+impl Resumable for Match {
+    fn resume(&self, cursor: Cursor, env: &mut Environment) -> Result<Step, RetErr> {
+        let (index, mut arm_env, cursor) = match cursor {
+            Cursor::Arm(index, arm_env, cursor) => (index, arm_env, *cursor),
+            _ => {
+                let (index, arm_env) = self.select(env)?;
+                (index, arm_env, Cursor::Start)
+            }
+        };
+
+        match self.arms[index].body.resume(cursor, &mut arm_env)? {
+            Step::Yielded(value, cursor) => Ok(Step::Yielded(
+                value,
+                Cursor::Arm(index, arm_env, Box::new(cursor)),
+            )),
+            Step::Finished => Ok(Step::Finished),
+        }
+    }
+}
+
+impl Resumable for Try {
+    fn resume(&self, cursor: Cursor, env: &mut Environment) -> Result<Step, RetErr> {
+        let result = match cursor {
+            Cursor::Finally(result, cursor) => return self.resume_finally(result, *cursor, env),
+            Cursor::Catch(catch_env, cursor) => self.resume_catch(catch_env, *cursor),
+            cursor => {
+                let cursor = match cursor {
+                    Cursor::Try(cursor) => *cursor,
+                    cursor => cursor,
+                };
+                match (self.body.resume(cursor, env), &self.catch) {
+                    (Ok(Step::Yielded(value, cursor)), _) => {
+                        return Ok(Step::Yielded(value, Cursor::Try(Box::new(cursor))))
+                    }
+                    (Err(RetErr::Error(exception)), Some(catch)) => {
+                        match exception.into_value(env) {
+                            Ok(value) => {
+                                let mut catch_env = env.append();
+                                match catch_env.declare(catch.name.clone(), value) {
+                                    Ok(()) => self.resume_catch(catch_env, Cursor::Start),
+                                    Err(exception) => Err(RetErr::Error(exception)),
+                                }
+                            }
+                            Err(exception) => Err(RetErr::Error(exception)),
+                        }
+                    }
+                    (result, _) => result,
+                }
+            }
+        };
+
+        match result {
+            Ok(Step::Yielded(value, cursor)) => Ok(Step::Yielded(value, cursor)),
+            Ok(Step::Finished) => self.resume_finally(Ok(()), Cursor::Start, env),
+            Err(err) => self.resume_finally(Err(err), Cursor::Start, env),
+        }
+    }
+}
+
+impl Try {
+    fn resume_catch(&self, mut catch_env: Environment, cursor: Cursor) -> Result<Step, RetErr> {
+        let catch = match &self.catch {
+            Some(catch) => catch,
+            None => return Ok(Step::Finished),
+        };
+
+        match catch.body.resume(cursor, &mut catch_env)? {
+            Step::Yielded(value, cursor) => Ok(Step::Yielded(
+                value,
+                Cursor::Catch(catch_env, Box::new(cursor)),
+            )),
+            Step::Finished => Ok(Step::Finished),
+        }
+    }
+
+    /// Runs the `finally` clause, if any, and then leaves the statement the
+    /// way its body or `catch` clause was left.
+    fn resume_finally(
+        &self,
+        result: Result<(), RetErr>,
+        cursor: Cursor,
+        env: &mut Environment,
+    ) -> Result<Step, RetErr> {
+        if let Some(finally) = &self.finally {
+            if let Step::Yielded(value, cursor) = finally.resume(cursor, env)? {
+                return Ok(Step::Yielded(
+                    value,
+                    Cursor::Finally(result, Box::new(cursor)),
+                ));
+            }
+        }
+
+        result.map(|()| Step::Finished)
+    }
+}
@@ -0,0 +1,111 @@
+use super::expression::{find_method, get_property};
+use super::{type_error, Callable, Exception};
+use crate::ast::{Primary, Range, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The items of a value being walked by a `for-in` loop.
+pub enum Iter {
+    /// The items of a list, read as they are reached so that items pushed
+    /// while looping are included.
+    List(Rc<RefCell<Vec<Rc<Value>>>>, usize),
+    /// The characters of a string, from a byte offset.
+    Chars(String, usize),
+    Range(Range),
+    /// A generator or an iterator instance. Each call to its `next` method
+    /// returns an item until its `done` property is truthy.
+    Object(Rc<Value>),
+}
+
+impl Iter {
+    /// Starts iterating over a value. Lists, strings, maps (over their sorted
+    /// keys), ranges and generators can be iterated, along with iterator
+    /// instances, or instances with an `iter` method returning an iterable.
+    ///
+    /// An iterator instance has a `next` method and a `done` field. Each call
+    /// to `next` returns an item, unless the items are exhausted, in which
+    /// case it sets `done` to a truthy value and what it returns is ignored.
+    pub fn new(value: &Rc<Value>) -> Result<Self, Exception> {
+        let mut value = value.clone();
+        // the instances whose `iter` method was called, to catch cycles
+        let mut visited: Vec<Rc<Value>> = vec![];
+        loop {
+            match &*value {
+                Value::Instance(instance) => {
+                    if find_method(&instance.class, &instance.class_env, "next").is_some() {
+                        if !instance.fields.borrow().contains_key(&"done".to_string()) {
+                            return Err(type_error(format!(
+                                "{} is not an iterator, it has a 'next' method but no 'done' field",
+                                value
+                            )));
+                        }
+                        return Ok(Iter::Object(value.clone()));
+                    }
+                    if find_method(&instance.class, &instance.class_env, "iter").is_none() {
+                        return Err(type_error(format!(
+                            "{} is not iterable, it has neither an 'iter' nor a 'next' method",
+                            value
+                        )));
+                    }
+                }
+                _ => return Iter::of(&value),
+            }
+
+            visited.push(value.clone());
+            let iter = get_property(&value, &Rc::new("iter".to_string()))?.call(vec![])?;
+            if visited.iter().any(|seen| Rc::ptr_eq(seen, &iter)) {
+                return Err(type_error(format!(
+                    "{} is not iterable, its 'iter' method leads back to {} without reaching a 'next' method",
+                    visited[0], iter
+                )));
+            }
+            value = iter;
+        }
+    }
+
+    /// Starts iterating over a value other than an instance.
+    fn of(value: &Rc<Value>) -> Result<Self, Exception> {
+        match &**value {
+            Value::List(list) => Ok(Iter::List(list.clone(), 0)),
+            Value::Map(map) => {
+                let mut keys: Vec<_> = map.borrow().keys().cloned().collect();
+                keys.sort();
+                let keys = keys.into_iter().map(|key| Rc::new(key.value())).collect();
+                Ok(Iter::List(Rc::new(RefCell::new(keys)), 0))
+            }
+            Value::Literal(Primary::String(string)) => Ok(Iter::Chars(string.clone(), 0)),
+            Value::Range(range) => Ok(Iter::Range(range.clone())),
+            Value::Generator(_) => Ok(Iter::Object(value.clone())),
+            value => Err(type_error(format!("{} is not iterable", value))),
+        }
+    }
+
+    /// Returns the next item, or `None` once the items are exhausted.
+    pub fn next(&mut self) -> Result<Option<Rc<Value>>, Exception> {
+        let item = match self {
+            Iter::List(list, index) => {
+                let item = list.borrow().get(*index).cloned();
+                *index += 1;
+                return Ok(item);
+            }
+            Iter::Chars(string, offset) => match string[*offset..].chars().next() {
+                Some(ch) => {
+                    *offset += ch.len_utf8();
+                    Primary::String(ch.to_string())
+                }
+                None => return Ok(None),
+            },
+            Iter::Range(range) => match range.next() {
+                Some(integer) => Primary::Integer(integer),
+                None => return Ok(None),
+            },
+            Iter::Object(iterator) => {
+                let item = get_property(iterator, &Rc::new("next".to_string()))?.call(vec![])?;
+                let done = get_property(iterator, &Rc::new("done".to_string()))?;
+                return Ok(if done.is_truthy() { None } else { Some(item) });
+            }
+        };
+
+        Ok(Some(Rc::new(Value::Literal(item))))
+    }
+}
@@ -1,6 +1,11 @@
 pub mod environment;
 mod expression;
+pub mod generator;
+mod iterator;
+pub mod module;
+pub mod native;
 mod statement;
+use std::fmt;
 use std::rc::Rc;
 
 use self::environment::Environment;
@@ -8,25 +13,43 @@ use super::ast::{Program, Value};
 
 enum RetErr {
     Return(Rc<Value>),
-    Error(String),
+    Break,
+    Continue,
+    Error(Exception),
+}
+
+/// An error that unwinds the program until a `try` statement catches it.
+pub enum Exception {
+    /// An error raised by the interpreter itself, with its kind (such as
+    /// `TypeError`) and message.
+    Error(&'static str, String),
+    /// A value thrown by a `throw` statement.
+    Thrown(Rc<Value>),
 }
 
 trait Evaluable {
-    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, String>;
+    fn evaluate(&self, env: &mut Environment) -> Result<Rc<Value>, Exception>;
 }
 
 trait Executable {
     fn execute(&self, env: &mut Environment) -> Result<(), RetErr>;
 }
 
+trait Callable {
+    fn call(&self, arguments: Vec<Rc<Value>>) -> Result<Rc<Value>, Exception>;
+}
+
 impl Program {
     pub fn run(&self, env: &mut Environment) -> Result<(), String> {
         use self::RetErr::*;
 
         for statement in &self.statements {
             match statement.execute(env) {
-                Err(Return(_)) => return Err(format!("Cannot have return outside a function")),
-                Err(Error(err)) => return Err(err),
+                Err(Return(_)) => return Err("Cannot have return outside a function".to_string()),
+                Err(Break) | Err(Continue) => {
+                    return Err("Cannot have break or continue outside a loop".to_string())
+                }
+                Err(Error(exception)) => return Err(exception.to_string()),
                 Ok(()) => (),
             };
         }
@@ -34,3 +57,70 @@ impl Program {
         Ok(())
     }
 }
+
+impl Exception {
+    /// Converts the exception into the value bound by a `catch` clause. An
+    /// interpreter error becomes an instance of the `Error` class of the
+    /// prelude.
+    fn into_value(self, env: &Environment) -> Result<Rc<Value>, Exception> {
+        use crate::ast::Primary;
+
+        match self {
+            Exception::Error(kind, message) => {
+                let class = match env.error_class() {
+                    Some(class) => class.clone(),
+                    None => return Err(Exception::Error(kind, message)),
+                };
+                let arguments = vec![
+                    Rc::new(Value::Literal(Primary::String(message))),
+                    Rc::new(Value::Literal(Primary::String(kind.to_string()))),
+                ];
+                class.call(arguments)
+            }
+            Exception::Thrown(value) => Ok(value),
+        }
+    }
+}
+
+fn type_error(message: String) -> Exception {
+    Exception::Error("TypeError", message)
+}
+
+fn name_error(message: String) -> Exception {
+    Exception::Error("NameError", message)
+}
+
+fn index_error(message: String) -> Exception {
+    Exception::Error("IndexError", message)
+}
+
+fn arithmetic_error(message: String) -> Exception {
+    Exception::Error("ArithmeticError", message)
+}
+
+impl From<String> for Exception {
+    fn from(message: String) -> Self {
+        Exception::Error("RuntimeError", message)
+    }
+}
+
+impl fmt::Display for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Exception::Error(_, message) => write!(f, "{}", message),
+            Exception::Thrown(value) => match &**value {
+                Value::Instance(instance) => {
+                    let fields = instance.fields.borrow();
+                    match (
+                        fields.get(&"kind".to_string()),
+                        fields.get(&"message".to_string()),
+                    ) {
+                        (Some(kind), Some(message)) => write!(f, "Uncaught {}: {}", kind, message),
+                        _ => write!(f, "Uncaught {}", value),
+                    }
+                }
+                value => write!(f, "Uncaught {}", value),
+            },
+        }
+    }
+}
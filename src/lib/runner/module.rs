@@ -0,0 +1,74 @@
+use super::environment::Environment;
+use crate::ast::{Module, Program, Statement};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Modules by canonical path. A module maps to `None` while it is being
+/// loaded, so that an import cycle can be reported.
+pub type Modules = HashMap<PathBuf, Option<Rc<Module>>>;
+
+/// Loads the module at `path`, relative to the directory of the importing
+/// module. A module is only run the first time it is imported.
+pub fn load(env: &Environment, path: &str) -> Result<Rc<Module>, String> {
+    let joined = env.dir().join(path);
+    let path = joined
+        .canonicalize()
+        .map_err(|err| format!("Cannot import '{}': {}", joined.display(), err))?;
+
+    match env.modules().borrow().get(&path) {
+        Some(Some(module)) => return Ok(module.clone()),
+        Some(None) => return Err(format!("Import cycle through '{}'", path.display())),
+        None => (),
+    }
+
+    env.modules().borrow_mut().insert(path.clone(), None);
+    let module = run(env, &path);
+    env.modules().borrow_mut().remove(&path);
+
+    let module = Rc::new(module?);
+    env.modules()
+        .borrow_mut()
+        .insert(path, Some(module.clone()));
+    Ok(module)
+}
+
+fn run(env: &Environment, path: &PathBuf) -> Result<Module, String> {
+    let code = fs::read_to_string(path)
+        .map_err(|err| format!("Cannot import '{}': {}", path.display(), err))?;
+    let program = crate::compile(&code, env.typing())
+        .map_err(|errors| format!("Cannot import '{}':\n{}", path.display(), errors.join("\n")))?;
+
+    let dir = path.parent().map(PathBuf::from).unwrap_or_default();
+    let mut module_env = env.module(dir);
+    crate::define_globals(&mut module_env);
+    program
+        .run(&mut module_env)
+        .map_err(|err| format!("Error in module '{}': {}", path.display(), err))?;
+
+    Ok(Module {
+        path: path.clone(),
+        env: module_env,
+        exports: exports(&program),
+    })
+}
+
+fn exports(program: &Program) -> Vec<Rc<String>> {
+    program
+        .statements
+        .iter()
+        .flat_map(|statement| match statement {
+            Statement::Export(declaration) => match &**declaration {
+                Statement::Declaration(declaration) => vec![declaration.name.clone()],
+                Statement::Destructuring(destructuring) => {
+                    destructuring.target.names().into_iter().cloned().collect()
+                }
+                Statement::Function(function) => vec![function.name.clone()],
+                Statement::Class(class) => vec![class.name.clone()],
+                _ => vec![],
+            },
+            _ => vec![],
+        })
+        .collect()
+}
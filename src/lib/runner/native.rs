@@ -0,0 +1,188 @@
+use super::environment::Environment;
+use super::expression::list_index;
+use crate::ast::{Key, Native, NativeFn, Primary, Range, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Lox source run before every program. It defines the `Error` class that
+/// runtime errors are converted into when they are caught.
+pub const PRELUDE: &str = "
+class Error {
+    init(message, kind = \"Error\") {
+        this.message = message;
+        this.kind = kind;
+    }
+}
+";
+
+/// Declares the builtin functions in the global environment.
+pub fn define_globals(env: &mut Environment) {
+    let natives: [(&'static str, (usize, usize), NativeFn); 11] = [
+        ("clock", (0, 0), clock),
+        ("len", (1, 1), len),
+        ("push", (2, 2), push),
+        ("pop", (1, 1), pop),
+        ("insert", (3, 3), insert),
+        ("remove", (2, 2), remove),
+        ("keys", (1, 1), keys),
+        ("values", (1, 1), values),
+        ("has", (2, 2), has),
+        ("delete", (2, 2), delete),
+        ("range", (1, 3), range),
+    ];
+
+    for &(name, arity, function) in natives.iter() {
+        // the global scope holds no constants yet, so this can't fail
+        let _ = env.declare(
+            Rc::new(name.to_string()),
+            Rc::new(Value::Native(Native {
+                name,
+                arity,
+                function,
+            })),
+        );
+    }
+}
+
+/// Returns the number of seconds elapsed since the Unix epoch.
+fn clock(_: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| err.to_string())?
+        .as_secs_f64();
+
+    Ok(Rc::new(Value::Literal(Primary::Number(seconds))))
+}
+
+/// Returns the number of items in a list or map, or of characters in a string.
+fn len(args: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    let len = match &*args[0] {
+        Value::List(list) => list.borrow().len(),
+        Value::Map(map) => map.borrow().len(),
+        Value::Literal(Primary::String(string)) => string.chars().count(),
+        value => return Err(format!("len() is not defined for {}", value)),
+    };
+
+    Ok(Rc::new(Value::Literal(Primary::Integer(len as i64))))
+}
+
+/// Appends an item to the end of a list and returns the list.
+fn push(args: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    as_list("push", &args[0])?
+        .borrow_mut()
+        .push(args[1].clone());
+    Ok(args[0].clone())
+}
+
+/// Removes the last item of a list and returns it.
+fn pop(args: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    as_list("pop", &args[0])?
+        .borrow_mut()
+        .pop()
+        .ok_or_else(|| "pop() from an empty list".to_string())
+}
+
+/// Inserts an item before the given index, which may be the list's length.
+fn insert(args: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    let mut list = as_list("insert", &args[0])?.borrow_mut();
+    let index = list_index(&args[1], list.len() + 1)?;
+    list.insert(index, args[2].clone());
+    Ok(args[0].clone())
+}
+
+/// Removes the item at the given index and returns it.
+fn remove(args: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    let mut list = as_list("remove", &args[0])?.borrow_mut();
+    let index = list_index(&args[1], list.len())?;
+    Ok(list.remove(index))
+}
+
+/// Returns the keys of a map as a sorted list.
+fn keys(args: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    let map = as_map("keys", &args[0])?.borrow();
+    let keys = sorted_keys(&map)
+        .into_iter()
+        .map(|key| Rc::new(key.value()))
+        .collect();
+    Ok(Rc::new(Value::list(keys)))
+}
+
+/// Returns the values of a map as a list, ordered by their keys.
+fn values(args: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    let map = as_map("values", &args[0])?.borrow();
+    let values = sorted_keys(&map)
+        .into_iter()
+        .map(|key| map[key].clone())
+        .collect();
+    Ok(Rc::new(Value::list(values)))
+}
+
+/// Returns whether a map contains the given key.
+fn has(args: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    let map = as_map("has", &args[0])?.borrow();
+    let found = map.contains_key(&Key::new(&args[1])?);
+    Ok(Rc::new(Value::Literal(Primary::Boolean(found))))
+}
+
+/// Removes a key from a map, returning its value or nil if it was absent.
+fn delete(args: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    let mut map = as_map("delete", &args[0])?.borrow_mut();
+    Ok(map
+        .remove(&Key::new(&args[1])?)
+        .unwrap_or_else(|| Rc::new(Value::Literal(Primary::Nil))))
+}
+
+/// Returns the range of integers from a start up to an end, counting by a
+/// step: `range(end)`, `range(start, end)` or `range(start, end, step)`.
+fn range(args: Vec<Rc<Value>>) -> Result<Rc<Value>, String> {
+    let mut bounds = vec![];
+    for arg in &args {
+        match **arg {
+            Value::Literal(Primary::Integer(integer)) => bounds.push(integer),
+            ref value => return Err(format!("range() expects integers, found {}", value)),
+        }
+    }
+
+    let range = match bounds[..] {
+        [end] => Range {
+            start: 0,
+            end,
+            step: 1,
+        },
+        [start, end] => Range {
+            start,
+            end,
+            step: 1,
+        },
+        [_, _, 0] => return Err("range() step cannot be zero".to_string()),
+        [start, end, step] => Range { start, end, step },
+        _ => unreachable!(),
+    };
+
+    Ok(Rc::new(Value::Range(range)))
+}
+
+fn sorted_keys(map: &HashMap<Key, Rc<Value>>) -> Vec<&Key> {
+    let mut keys: Vec<_> = map.keys().collect();
+    keys.sort();
+    keys
+}
+
+fn as_map<'a>(
+    name: &str,
+    value: &'a Value,
+) -> Result<&'a RefCell<HashMap<Key, Rc<Value>>>, String> {
+    match value {
+        Value::Map(map) => Ok(map),
+        value => Err(format!("{}() expects a map, found {}", name, value)),
+    }
+}
+
+fn as_list<'a>(name: &str, value: &'a Value) -> Result<&'a RefCell<Vec<Rc<Value>>>, String> {
+    match value {
+        Value::List(list) => Ok(list),
+        value => Err(format!("{}() expects a list, found {}", name, value)),
+    }
+}
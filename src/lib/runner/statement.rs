@@ -1,29 +1,56 @@
 use super::environment::Environment;
-use super::{Evaluable, Executable, RetErr};
+use super::expression::{assign, check_type, get_property};
+use super::iterator::Iter;
+use super::module;
+use super::{index_error, name_error, type_error, Evaluable, Exception, Executable, RetErr};
 use crate::ast::Statement::*;
-use crate::ast::{Block, Conditional, Declaration, Function, Iteration, Primary, Statement, Value};
+use crate::ast::{
+    Assignments, Block, Class, Conditional, Declaration, Destructuring, ForIn, Function, Import,
+    ImportNames, Iteration, Key, Match, Pattern, Primary, Statement, Target, Try, Value,
+};
 use std::rc::Rc;
 
 impl Executable for Statement {
     fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
         match self {
             Expression(expression) => {
-                expression.evaluate(env).map_err(|err| RetErr::Error(err))?;
+                expression.evaluate(env).map_err(RetErr::Error)?;
                 Ok(())
             }
             Print(expression) => {
-                let result = expression.evaluate(env).map_err(|err| RetErr::Error(err))?;
-                Ok(println!("{}", result))
+                let result = expression.evaluate(env).map_err(RetErr::Error)?;
+                println!("{}", result);
+                Ok(())
             }
             Declaration(declaration) => declaration.execute(env),
+            Destructuring(destructuring) => destructuring.execute(env),
+            Assignments(assignments) => assignments.execute(env),
             Function(function) => function.execute(env),
+            Class(class) => class.execute(env),
             Block(block) => block.execute(env),
             Conditional(conditional) => conditional.execute(env),
             Iteration(iteration) => iteration.execute(env),
-            Return(expression) => {
-                let value = expression.evaluate(env).map_err(|err| RetErr::Error(err))?;
+            ForIn(for_in) => for_in.execute(env),
+            Match(matching) => matching.execute(env),
+            Try(try_statement) => try_statement.execute(env),
+            Throw(expression) => {
+                let value = expression.evaluate(env).map_err(RetErr::Error)?;
+                Err(RetErr::Error(Exception::Thrown(value)))
+            }
+            Import(import) => import.execute(env),
+            Export(declaration) => declaration.execute(env),
+            Return(expression, _) => {
+                let value = match expression {
+                    Some(expression) => expression.evaluate(env).map_err(RetErr::Error)?,
+                    None => Rc::new(Value::Literal(Primary::Nil)),
+                };
                 Err(RetErr::Return(value))
             }
+            Yield(..) => Err(RetErr::Error(Exception::from(
+                "Cannot yield outside of a generator".to_string(),
+            ))),
+            Break(_) => Err(RetErr::Break),
+            Continue(_) => Err(RetErr::Continue),
         }
     }
 }
@@ -31,11 +58,156 @@ impl Executable for Statement {
 impl Executable for Declaration {
     fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
         let value = match &self.value {
-            Some(expression) => expression.evaluate(env).map_err(|err| RetErr::Error(err))?,
+            Some(expression) => expression.evaluate(env).map_err(RetErr::Error)?,
             None => Rc::new(Value::Literal(Primary::Nil)),
         };
 
-        env.declare(self.name.clone(), value);
+        if self.constant {
+            env.declare_constant(self.name.clone(), value)
+        } else {
+            env.declare(self.name.clone(), value)
+        }
+        .map_err(RetErr::Error)
+    }
+}
+
+impl Executable for Destructuring {
+    fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
+        let value = self.value.evaluate(env).map_err(RetErr::Error)?;
+        let mut bindings = vec![];
+        unpack(&self.target, value, &mut bindings).map_err(RetErr::Error)?;
+
+        for (name, value) in bindings {
+            if self.constant {
+                env.declare_constant(name, value)
+            } else {
+                env.declare(name, value)
+            }
+            .map_err(RetErr::Error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Unpacks a value into the names of a target, collecting the names along
+/// with their values. Errors name the position in the target that failed.
+fn unpack(
+    target: &Target,
+    value: Rc<Value>,
+    bindings: &mut Vec<(Rc<String>, Rc<Value>)>,
+) -> Result<(), Exception> {
+    match target {
+        Target::Name(name) => bindings.push((name.clone(), value)),
+        Target::List(targets, rest) => {
+            let items = match &*value {
+                Value::List(list) => list.borrow().clone(),
+                value => {
+                    return Err(type_error(format!(
+                        "Cannot unpack {} into {}, expected a list",
+                        value, target
+                    )))
+                }
+            };
+
+            if items.len() < targets.len() {
+                return Err(index_error(format!(
+                    "Cannot unpack {} into {}, no value for {} at position {}",
+                    value,
+                    target,
+                    targets[items.len()],
+                    items.len() + 1
+                )));
+            }
+            if rest.is_none() && items.len() > targets.len() {
+                return Err(index_error(format!(
+                    "Cannot unpack {} into {}, too many values after position {}",
+                    value,
+                    target,
+                    targets.len()
+                )));
+            }
+
+            let mut items = items.into_iter();
+            for (target, item) in targets.iter().zip(&mut items) {
+                unpack(target, item, bindings)?;
+            }
+            if let Some(rest) = rest {
+                bindings.push((rest.clone(), Rc::new(Value::list(items.collect()))));
+            }
+        }
+        Target::Fields(names) => {
+            for (position, name) in names.iter().enumerate() {
+                let field = match &*value {
+                    Value::Map(map) => {
+                        let key = Key::new(&Value::Literal(Primary::String(name.to_string())))?;
+                        map.borrow().get(&key).cloned()
+                    }
+                    Value::Instance(_) => get_property(&value, name).ok(),
+                    value => {
+                        return Err(type_error(format!(
+                            "Cannot unpack {} into {}, expected an instance or a map",
+                            value, target
+                        )))
+                    }
+                };
+                let field = field.ok_or_else(|| {
+                    name_error(format!(
+                        "Cannot unpack {} into {}, no field '{}' at position {}",
+                        value,
+                        target,
+                        name,
+                        position + 1
+                    ))
+                })?;
+                bindings.push((name.clone(), field));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl Executable for Assignments {
+    fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
+        let mut values = vec![];
+        for value in &self.values {
+            values.push(value.evaluate(env).map_err(RetErr::Error)?);
+        }
+
+        if values.len() == 1 && self.targets.len() > 1 {
+            let count = self.targets.len();
+            values = match &*values[0] {
+                Value::List(list) if list.borrow().len() == count => list.borrow().clone(),
+                Value::List(list) => {
+                    let found = list.borrow().len();
+                    let message = if found < count {
+                        format!("no value for the target at position {}", found + 1)
+                    } else {
+                        format!("too many values after position {}", count)
+                    };
+                    return Err(RetErr::Error(index_error(format!(
+                        "Cannot unpack {} into {} targets, {}",
+                        values[0], count, message
+                    ))));
+                }
+                value => {
+                    return Err(RetErr::Error(type_error(format!(
+                        "Cannot unpack {} into {} targets, expected a list",
+                        value, count
+                    ))))
+                }
+            };
+        }
+
+        for (value, check) in values.iter().zip(&self.checks) {
+            if let Some((annotation, context)) = check {
+                check_type(value.clone(), annotation, context).map_err(RetErr::Error)?;
+            }
+        }
+
+        for (target, value) in self.targets.iter().zip(values) {
+            assign(target, value, env).map_err(RetErr::Error)?;
+        }
         Ok(())
     }
 }
@@ -45,9 +217,36 @@ impl Executable for Function {
         let func_env = env.clone();
         env.declare(
             self.name.clone(),
-            Rc::new(Value::Function((*self).clone(), func_env)),
-        );
-        Ok(())
+            Rc::new(Value::Function(Rc::new(self.clone()), func_env)),
+        )
+        .map_err(RetErr::Error)
+    }
+}
+
+impl Executable for Class {
+    fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
+        let mut class_env = env.append();
+
+        if let Some(superclass) = &self.superclass {
+            let superclass = superclass.evaluate(env).map_err(RetErr::Error)?;
+            match *superclass {
+                Value::Class(..) => class_env
+                    .declare(Rc::new("super".to_string()), superclass)
+                    .map_err(RetErr::Error)?,
+                ref value => {
+                    return Err(RetErr::Error(type_error(format!(
+                        "Superclass of {} must be a class, found {}",
+                        self.name, value
+                    ))))
+                }
+            }
+        }
+
+        env.declare(
+            self.name.clone(),
+            Rc::new(Value::Class(Rc::new(self.clone()), class_env)),
+        )
+        .map_err(RetErr::Error)
     }
 }
 
@@ -63,12 +262,7 @@ impl Executable for Block {
 
 impl Executable for Conditional {
     fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
-        if self
-            .cond
-            .evaluate(env)
-            .map_err(|err| RetErr::Error(err))?
-            .is_truthy()
-        {
+        if self.cond.evaluate(env).map_err(RetErr::Error)?.is_truthy() {
             self.success.execute(env)?;
         } else if let Some(failure) = &self.failure {
             failure.execute(env)?;
@@ -80,15 +274,179 @@ impl Executable for Conditional {
 
 impl Executable for Iteration {
     fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
-        while self
-            .cond
-            .evaluate(env)
-            .map_err(|err| RetErr::Error(err))?
-            .is_truthy()
-        {
-            self.body.execute(env)?;
+        while self.cond.evaluate(env).map_err(RetErr::Error)?.is_truthy() {
+            match self.body.execute(env) {
+                Err(RetErr::Break) => break,
+                Err(RetErr::Continue) | Ok(()) => (),
+                Err(err) => return Err(err),
+            }
+
+            if let Some(increment) = &self.increment {
+                increment.evaluate(env).map_err(RetErr::Error)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Executable for ForIn {
+    fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
+        let iterable = self.iterable.evaluate(env).map_err(RetErr::Error)?;
+        let mut items = Iter::new(&iterable).map_err(RetErr::Error)?;
+
+        while let Some(item) = items.next().map_err(RetErr::Error)? {
+            let mut loop_env = env.append();
+            loop_env
+                .declare(self.name.clone(), item)
+                .map_err(RetErr::Error)?;
+
+            match self.body.execute(&mut loop_env) {
+                Err(RetErr::Break) => break,
+                Err(RetErr::Continue) | Ok(()) => (),
+                Err(err) => return Err(err),
+            }
         }
 
         Ok(())
     }
 }
+
+impl Executable for Import {
+    fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
+        let module = module::load(env, &self.path)
+            .map_err(|message| RetErr::Error(Exception::Error("ImportError", message)))?;
+
+        match &self.names {
+            ImportNames::Module(name) => env
+                .declare(name.clone(), Rc::new(Value::Module(module)))
+                .map_err(RetErr::Error)?,
+            ImportNames::Exports(names) => {
+                for name in names {
+                    let value = module.get(name).ok_or_else(|| {
+                        RetErr::Error(Exception::Error(
+                            "ImportError",
+                            format!(
+                                "Module '{}' has no export '{}'",
+                                module.path.display(),
+                                name
+                            ),
+                        ))
+                    })?;
+                    env.declare(name.clone(), value).map_err(RetErr::Error)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Executable for Try {
+    fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
+        let result = match (self.body.execute(env), &self.catch) {
+            (Err(RetErr::Error(exception)), Some(catch)) => match exception.into_value(env) {
+                Ok(value) => {
+                    let mut catch_env = env.append();
+                    catch_env
+                        .declare(catch.name.clone(), value)
+                        .map_err(RetErr::Error)?;
+                    catch.body.execute(&mut catch_env)
+                }
+                Err(exception) => Err(RetErr::Error(exception)),
+            },
+            (result, _) => result,
+        };
+
+        // `finally` runs however the body was left, and a `return`, `break`
+        // or error inside it replaces the body's outcome
+        if let Some(finally) = &self.finally {
+            finally.execute(env)?;
+        }
+
+        result
+    }
+}
+
+impl Executable for Match {
+    fn execute(&self, env: &mut Environment) -> Result<(), RetErr> {
+        let (index, mut arm_env) = self.select(env)?;
+        self.arms[index].body.execute(&mut arm_env)
+    }
+}
+
+impl Match {
+    /// Finds the first arm matching the value, and returns its index along
+    /// with the environment holding the names bound by its patterns.
+    pub(super) fn select(&self, env: &mut Environment) -> Result<(usize, Environment), RetErr> {
+        let value = self.value.evaluate(env).map_err(RetErr::Error)?;
+
+        for (index, arm) in self.arms.iter().enumerate() {
+            let mut arm_env = env.append();
+            let mut matched = false;
+            for pattern in &arm.patterns {
+                if matches(pattern, &value, &mut arm_env).map_err(RetErr::Error)? {
+                    matched = true;
+                    break;
+                }
+            }
+            if !matched {
+                continue;
+            }
+
+            if let Some(guard) = &arm.guard {
+                if !guard
+                    .evaluate(&mut arm_env)
+                    .map_err(RetErr::Error)?
+                    .is_truthy()
+                {
+                    continue;
+                }
+            }
+
+            return Ok((index, arm_env));
+        }
+
+        Err(RetErr::Error(Exception::Error(
+            "MatchError",
+            format!(
+                "No match arm matches {} of type {}",
+                value,
+                value.type_name()
+            ),
+        )))
+    }
+}
+
+/// Tests `value` against `pattern`, declaring the name of a binding pattern
+/// in `env`.
+fn matches(pattern: &Pattern, value: &Rc<Value>, env: &mut Environment) -> Result<bool, Exception> {
+    let matched = match pattern {
+        Pattern::Literal(primary) => **value == Value::Literal(primary.clone()),
+        Pattern::Binding(name) => {
+            env.declare(name.clone(), value.clone())?;
+            true
+        }
+        Pattern::Wildcard => true,
+        Pattern::Range(start, end) => match &**value {
+            Value::Literal(primary) => match (number(start), number(primary), number(end)) {
+                (Some(start), Some(number), Some(end)) => start <= number && number < end,
+                _ => false,
+            },
+            _ => false,
+        },
+        Pattern::Type(name) => match name.as_str() {
+            "number" => matches!(value.type_name(), "int" | "float"),
+            name => value.type_name() == name,
+        },
+    };
+    Ok(matched)
+}
+
+fn number(primary: &Primary) -> Option<f64> {
+    match primary {
+        Primary::Integer(integer) => Some(*integer as f64),
+        Primary::Number(number) => Some(*number),
+        _ => None,
+    }
+}
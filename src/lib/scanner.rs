@@ -7,24 +7,32 @@ use std::str::Chars;
 
 pub struct Scanner<'a> {
     source: MultiPeek<Chars<'a>>,
+    text: &'a str,
+    position: Position,
     start_line: usize,
     end_line: usize,
     start_char: usize,
     end_char: usize,
     errors: Vec<String>,
     literal: String,
+    interpolations: Vec<usize>,
+    doc: Option<String>,
 }
 
 impl<'a> Scanner<'a> {
     pub fn new(source: &'a str) -> Self {
         Scanner {
             source: multipeek(source.chars()),
+            text: source,
+            position: Position::start(),
             start_line: 1,
             end_line: 1,
             start_char: 0,
             end_char: 0,
             errors: vec![],
             literal: String::new(),
+            interpolations: vec![],
+            doc: None,
         }
     }
 
@@ -39,23 +47,47 @@ impl<'a> Scanner<'a> {
         match self.source.next() {
             Some('(') => self.token(LEFT_PAREN),
             Some(')') => self.token(RIGHT_PAREN),
-            Some('{') => self.token(LEFT_BRACE),
-            Some('}') => self.token(RIGHT_BRACE),
-            Some('.') => self.token(DOT),
+            Some('{') => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                self.token(LEFT_BRACE)
+            }
+            Some('}') => match self.interpolations.last_mut() {
+                Some(0) => {
+                    self.interpolations.pop();
+                    self.scan_string()
+                }
+                Some(depth) => {
+                    *depth -= 1;
+                    self.token(RIGHT_BRACE)
+                }
+                None => self.token(RIGHT_BRACE),
+            },
+            Some('[') => self.token(LEFT_BRACKET),
+            Some(']') => self.token(RIGHT_BRACKET),
+            Some('.') => self.scan_dot(),
             Some(',') => self.token(COMMA),
-            Some('-') => self.token(HYPHEN),
-            Some('+') => self.token(PLUS),
+            Some(':') => self.token(COLON),
+            Some('?') => self.token(QUESTION),
+            Some('-') => self.scan_hyphen(),
+            Some('+') => self.match_double('+', PLUS, PLUS_EQUAL, PLUS_PLUS),
             Some(';') => self.token(SEMICOLON),
-            Some('*') => self.token(ASTERICS),
-            Some('!') => self.match_equal(BANG, BANG_EQUAL),
-            Some('=') => self.match_equal(EQUAL, EQUAL_EQUAL),
-            Some('<') => self.match_equal(LESS, LESS_EQUAL),
-            Some('>') => self.match_equal(GREATER, GREATER_EQUAL),
+            Some('*') => self.match_double('*', ASTERICS, ASTERICS_EQUAL, ASTERICS_ASTERICS),
+            Some('%') => self.token(PERCENT),
+            Some('&') => self.token(AMPERSAND),
+            Some('|') => self.token(PIPE),
+            Some('^') => self.token(CARET),
+            Some('~') => self.match_char('/', TILDE, TILDE_SLASH),
+            Some('!') => self.match_char('=', BANG, BANG_EQUAL),
+            Some('=') => self.scan_equal(),
+            Some('<') => self.match_double('<', LESS, LESS_EQUAL, LESS_LESS),
+            Some('>') => self.match_double('>', GREATER, GREATER_EQUAL, GREATER_GREATER),
             Some('/') => self.scan_comment(),
             Some(' ') | Some('\r') | Some('\t') => self.next_token(),
             Some('"') => self.scan_string(),
-            Some(ch @ '0'...'9') => self.scan_number(ch),
-            Some(ch) if is_alpha(ch) => self.scan_identifier(ch),
+            Some(ch @ '0'..='9') => self.scan_number(ch),
+            Some(ch) if is_xid_start(ch) => self.scan_identifier(ch),
             Some('\n') => {
                 self.newline();
                 self.next_token()
@@ -72,12 +104,48 @@ impl<'a> Scanner<'a> {
         }
     }
 
-    fn token(&self, token_type: TokenType) -> Option<Token> {
-        Some(Token::new(token_type, self.start_line, self.start_char))
+    fn token(&mut self, token_type: TokenType) -> Option<Token> {
+        self.seek(self.start_line, self.start_char);
+        let mut token = Token::new(
+            token_type,
+            self.start_line,
+            self.start_char,
+            self.position.utf16,
+            self.position.byte,
+        );
+        token.doc = self.doc.take();
+        Some(token)
+    }
+
+    /// Moves `position` to the given line and char column. Tokens are
+    /// created in order, so the source is only walked once overall, but an
+    /// error can point back into a token, which walks again from the start.
+    fn seek(&mut self, line: usize, column: usize) {
+        let position = &mut self.position;
+        if (line, column) < (position.line, position.column) {
+            *position = Position::start();
+        }
+        let mut rest = self.text[position.byte..].chars();
+        while (position.line, position.column) < (line, column) {
+            match rest.next() {
+                Some('\n') => {
+                    position.line += 1;
+                    position.column = 1;
+                    position.utf16 = 1;
+                    position.byte += 1;
+                }
+                Some(ch) => {
+                    position.column += 1;
+                    position.utf16 += ch.len_utf16();
+                    position.byte += ch.len_utf8();
+                }
+                None => break,
+            }
+        }
     }
 
-    fn match_equal(&mut self, failure: TokenType, success: TokenType) -> Option<Token> {
-        let token_type = if Some(true) == match_next!(self.source, '=') {
+    fn match_char(&mut self, ch: char, failure: TokenType, success: TokenType) -> Option<Token> {
+        let token_type = if Some(true) == match_next!(self.source, ch) {
             self.end_char += 1;
             success
         } else {
@@ -87,71 +155,351 @@ impl<'a> Scanner<'a> {
         self.token(token_type)
     }
 
+    /// Matches an operator that can be doubled or followed by `=`, such as
+    /// `<`, `<<` and `<=`.
+    fn match_double(
+        &mut self,
+        ch: char,
+        single: TokenType,
+        equal: TokenType,
+        double: TokenType,
+    ) -> Option<Token> {
+        if Some(true) == match_next!(self.source, ch) {
+            self.end_char += 1;
+            self.token(double)
+        } else {
+            self.source.reset_peek();
+            self.match_char('=', single, equal)
+        }
+    }
+
+    fn scan_hyphen(&mut self) -> Option<Token> {
+        if Some(true) == match_next!(self.source, '>') {
+            self.end_char += 1;
+            self.token(THIN_ARROW)
+        } else {
+            self.source.reset_peek();
+            self.match_double('-', HYPHEN, HYPHEN_EQUAL, HYPHEN_HYPHEN)
+        }
+    }
+
+    fn scan_equal(&mut self) -> Option<Token> {
+        if Some(true) == match_next!(self.source, '>') {
+            self.end_char += 1;
+            self.token(ARROW)
+        } else {
+            self.source.reset_peek();
+            self.match_char('=', EQUAL, EQUAL_EQUAL)
+        }
+    }
+
+    fn scan_dot(&mut self) -> Option<Token> {
+        if Some(true) == match_next!(self.source, '.') {
+            self.end_char += 1;
+            self.match_char('.', DOT_DOT, ELLIPSIS)
+        } else {
+            self.source.reset_peek();
+            self.token(DOT)
+        }
+    }
+
     fn scan_comment(&mut self) -> Option<Token> {
         if Some(true) == match_next!(self.source, '/') {
             self.end_char += 1;
-            while Some(false) == match_next!(self.source, '\n') {
-                self.source.next();
+            return self.scan_line_comment();
+        }
+        self.source.reset_peek();
+
+        if Some(true) == match_next!(self.source, '*') {
+            self.end_char += 1;
+            return self.scan_block_comment();
+        }
+        self.source.reset_peek();
+
+        self.match_char('=', SLASH, SLASH_EQUAL)
+    }
+
+    /// Skips a `//` comment. A comment that starts with exactly three slashes
+    /// is a doc comment, and its text is attached to the next token.
+    fn scan_line_comment(&mut self) -> Option<Token> {
+        let mut text = String::new();
+        loop {
+            match self.advance() {
+                Some('\n') => {
+                    self.newline();
+                    break;
+                }
+                Some(ch) => text.push(ch),
+                None => break,
+            }
+        }
+
+        if text.starts_with('/') && !text.starts_with("//") {
+            let line = text[1..].trim_end();
+            let line = line.strip_prefix(' ').unwrap_or(line);
+            match &mut self.doc {
+                Some(doc) => {
+                    doc.push('\n');
+                    doc.push_str(line);
+                }
+                None => self.doc = Some(line.to_string()),
             }
-            self.newline();
-            self.next_token()
-        } else {
-            self.token(SLASH)
         }
+
+        self.next_token()
     }
 
+    /// Skips a `/* */` comment, which may contain nested block comments.
+    fn scan_block_comment(&mut self) -> Option<Token> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.advance() {
+                Some('/') if Some(true) == match_next!(self.source, '*') => {
+                    self.end_char += 1;
+                    depth += 1;
+                }
+                Some('*') if Some(true) == match_next!(self.source, '/') => {
+                    self.end_char += 1;
+                    depth -= 1;
+                }
+                Some('\n') => self.newline(),
+                Some(_) => (),
+                None => {
+                    self.push_error(
+                        self.start_line,
+                        self.start_char,
+                        "Unterminated block comment",
+                    );
+                    return None;
+                }
+            }
+        }
+
+        self.next_token()
+    }
+
+    /// Scans the rest of a string literal. A `${` inside the string ends the
+    /// current token as an `INTERPOLATION`; the scanner resumes the string
+    /// once the matching `}` is reached.
     fn scan_string(&mut self) -> Option<Token> {
-        while Some(false) == check_next!(self.source, '"') {
-            if let Some(ch) = self.source.next() {
-                self.put_char(ch);
-                if ch == '\n' {
+        loop {
+            match self.source.next() {
+                None => {
+                    self.push_error(self.start_line, self.start_char, "Unterminated string.");
+                    return None;
+                }
+                Some('"') => {
+                    self.end_char += 1;
+                    let token_type = STRING(self.get_literal());
+                    return self.token(token_type);
+                }
+                Some('$') if Some(true) == match_next!(self.source, '{') => {
+                    self.end_char += 2;
+                    self.interpolations.push(0);
+                    let token_type = INTERPOLATION(self.get_literal());
+                    return self.token(token_type);
+                }
+                Some('\\') => self.scan_escape(),
+                Some('\n') => {
+                    self.literal.push('\n');
                     self.newline();
                 }
+                Some(ch) => self.put_char(ch),
             }
         }
+    }
 
-        match self.source.next() {
-            None => {
-                self.push_error(self.start_line, self.start_char, "Unterminated string.");
-                None
-            }
-            Some(_) => {
-                self.end_char += 1;
-                let token_type = STRING(self.get_literal());
-                self.token(token_type)
+    fn scan_escape(&mut self) {
+        let (line, offset) = (self.end_line, self.end_char + 1);
+        self.end_char += 1;
+
+        let escaped = match self.advance() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some('\\') => '\\',
+            Some('"') => '"',
+            Some('\'') => '\'',
+            Some('$') => '$',
+            Some('u') => match self.scan_unicode_escape() {
+                Some(ch) => ch,
+                None => {
+                    self.push_error(line, offset, "Invalid unicode escape sequence");
+                    return;
+                }
+            },
+            Some(ch) => {
+                self.push_error(line, offset, format!("Invalid escape sequence '\\{}'", ch));
+                return;
             }
+            None => return,
+        };
+
+        self.literal.push(escaped);
+    }
+
+    /// Scans the `{XXXX}` part of a `\u{XXXX}` escape, holding one to six hex
+    /// digits of a Unicode scalar value.
+    fn scan_unicode_escape(&mut self) -> Option<char> {
+        if Some(true) != match_next!(self.source, '{') {
+            self.source.reset_peek();
+            return None;
+        }
+        self.end_char += 1;
+
+        let mut digits = String::new();
+        while check_func!(self.source, is_hex_digit) {
+            digits.extend(self.advance());
+        }
+        self.source.reset_peek();
+
+        if Some(true) != match_next!(self.source, '}') {
+            self.source.reset_peek();
+            return None;
+        }
+        self.end_char += 1;
+
+        if digits.is_empty() || digits.len() > 6 {
+            return None;
         }
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(std::char::from_u32)
     }
 
+    /// Scans a number literal. Integers can be written in decimal, or in
+    /// hexadecimal, binary or octal after a `0x`, `0b` or `0o` prefix. A
+    /// decimal literal with a fraction or an exponent is a float. Digits can
+    /// be separated with `_`.
     fn scan_number(&mut self, first_digit: char) -> Option<Token> {
+        if first_digit == '0' {
+            let radix = match self.source.peek() {
+                Some('x') | Some('X') => Some(16),
+                Some('b') | Some('B') => Some(2),
+                Some('o') | Some('O') => Some(8),
+                _ => None,
+            };
+            self.source.reset_peek();
+            if let Some(radix) = radix {
+                if let Some(prefix) = self.advance() {
+                    return self.scan_radix_number(prefix, radix);
+                }
+            }
+        }
+
         self.literal.push(first_digit);
+        self.scan_digits(10);
 
-        while check_func!(self.source, is_digit) {
-            if let Some(digit) = self.source.next() {
-                self.put_char(digit);
+        let mut float = false;
+        if Some(true) == check_next!(self.source, '.') && check_func!(self.source, is_digit) {
+            float = true;
+            if let Some(ch) = self.source.next() {
+                self.put_char(ch);
             }
+            self.scan_digits(10);
         }
         self.source.reset_peek();
 
-        if Some(true) == check_next!(self.source, '.') && check_func!(self.source, is_digit) {
+        if Some(true) == check_next!(self.source, 'e', 'E') {
+            float = true;
             if let Some(ch) = self.source.next() {
                 self.put_char(ch);
             }
-            while check_func!(self.source, is_digit) {
-                if let Some(digit) = self.source.next() {
-                    self.put_char(digit)
+            if Some(true) == check_next!(self.source, '+', '-') {
+                if let Some(ch) = self.source.next() {
+                    self.put_char(ch);
                 }
             }
+            self.source.reset_peek();
+            let digits = check_func!(self.source, is_digit);
+            self.source.reset_peek();
+            if !digits {
+                let literal = self.get_literal();
+                return self
+                    .number_error(format!("Expected digits in the exponent of {}", literal));
+            }
+            self.scan_digits(10);
         }
 
-        let token_type = NUMBER(self.get_literal().parse().unwrap());
-        self.token(token_type)
+        let literal = self.get_literal();
+        let digits = match strip_separators(&literal, 10) {
+            Some(digits) => digits,
+            None => return self.number_error(format!("Misplaced '_' in number {}", literal)),
+        };
+
+        if float {
+            return match digits.parse::<f64>() {
+                Ok(number) if number.is_finite() => self.token(NUMBER(number)),
+                Ok(_) => self.number_error(format!("Float literal {} is too large", literal)),
+                Err(_) => self.number_error(format!("Invalid float literal {}", literal)),
+            };
+        }
+
+        match digits.parse() {
+            Ok(integer) => self.token(INTEGER(integer)),
+            Err(_) => self.number_error(format!("Integer literal {} is too large", literal)),
+        }
+    }
+
+    /// Scans the digits of an integer after a `0x`, `0b` or `0o` prefix.
+    fn scan_radix_number(&mut self, prefix: char, radix: u32) -> Option<Token> {
+        self.scan_digits(radix);
+
+        let mut invalid = String::new();
+        while check_func!(self.source, is_xid_continue) {
+            if let Some(ch) = self.source.next() {
+                invalid.push(ch);
+                self.end_char += 1;
+            }
+        }
+        self.source.reset_peek();
+
+        let literal = format!("0{}{}", prefix, self.get_literal());
+        if let Some(ch) = invalid.chars().next() {
+            return self.number_error(format!(
+                "Invalid digit '{}' in number {}{}",
+                ch, literal, invalid
+            ));
+        }
+
+        let digits = match strip_separators(&literal[2..], radix) {
+            Some(digits) if digits.is_empty() => {
+                return self.number_error(format!("Expected digits after '{}'", literal))
+            }
+            Some(digits) => digits,
+            None => return self.number_error(format!("Misplaced '_' in number {}", literal)),
+        };
+
+        match i64::from_str_radix(&digits, radix) {
+            Ok(integer) => self.token(INTEGER(integer)),
+            Err(_) => self.number_error(format!("Integer literal {} is too large", literal)),
+        }
+    }
+
+    /// Scans digits of the given radix, along with any `_` separators.
+    fn scan_digits(&mut self, radix: u32) {
+        while let Some(&ch) = self.source.peek() {
+            if !ch.is_digit(radix) && ch != '_' {
+                break;
+            }
+            self.source.next();
+            self.put_char(ch);
+        }
+        self.source.reset_peek();
+    }
+
+    /// Reports a malformed number literal. A number is still produced so the
+    /// parser doesn't report errors that follow from this one.
+    fn number_error(&mut self, message: String) -> Option<Token> {
+        self.push_error(self.start_line, self.start_char, message);
+        self.token(INTEGER(0))
     }
 
     fn scan_identifier(&mut self, ch: char) -> Option<Token> {
         self.literal.push(ch);
 
-        while check_func!(self.source, is_alpha, is_digit) {
+        while check_func!(self.source, is_xid_continue) {
             if let Some(ch) = self.source.next() {
                 self.put_char(ch);
             }
@@ -172,18 +520,29 @@ impl<'a> Scanner<'a> {
         self.end_char = 0;
     }
 
+    fn advance(&mut self) -> Option<char> {
+        let ch = self.source.next();
+        if ch.is_some() {
+            self.end_char += 1;
+        }
+        ch
+    }
+
     fn put_char(&mut self, ch: char) {
         self.literal.push(ch);
         self.end_char += 1;
     }
 
     fn get_literal(&mut self) -> String {
-        mem::replace(&mut self.literal, String::new())
+        mem::take(&mut self.literal)
     }
 
+    /// Reports an error at a line and char column, printing the column in
+    /// UTF-16 code units like the other diagnostics.
     fn push_error<T: Display>(&mut self, line: usize, offset: usize, message: T) {
+        self.seek(line, offset);
         self.errors
-            .push(format!("L{}:{} {}", line, offset, message));
+            .push(format!("L{}:{} {}", line, self.position.utf16, message));
     }
 }
 
@@ -195,32 +554,90 @@ impl<'a> Iterator for Scanner<'a> {
     }
 }
 
-fn is_alpha(ch: char) -> bool {
-    ('a' <= ch && ch <= 'z') || ('A' <= ch && ch <= 'Z') || ch == '_'
+/// A position in the source, as a line along with the column of the same
+/// char counted in chars and in UTF-16 code units, and its byte offset.
+struct Position {
+    line: usize,
+    column: usize,
+    utf16: usize,
+    byte: usize,
+}
+
+impl Position {
+    fn start() -> Self {
+        Position {
+            line: 1,
+            column: 1,
+            utf16: 1,
+            byte: 0,
+        }
+    }
+}
+
+/// Whether `ch` can start an identifier, which is `XID_Start` from Unicode
+/// Standard Annex #31 along with `_`.
+fn is_xid_start(ch: char) -> bool {
+    ch == '_' || unicode_ident::is_xid_start(ch)
+}
+
+fn is_xid_continue(ch: char) -> bool {
+    unicode_ident::is_xid_continue(ch)
 }
 
 fn is_digit(ch: char) -> bool {
-    '0' <= ch && ch <= '9'
+    ch.is_ascii_digit()
+}
+
+/// Removes the `_` separators from the digits of a number literal, or
+/// returns `None` if a separator isn't between two digits.
+fn strip_separators(literal: &str, radix: u32) -> Option<String> {
+    let chars: Vec<char> = literal.chars().collect();
+    for (i, &ch) in chars.iter().enumerate() {
+        let digit = |j: Option<usize>| {
+            j.and_then(|j| chars.get(j))
+                .is_some_and(|c| c.is_digit(radix))
+        };
+        if ch == '_' && !(digit(i.checked_sub(1)) && digit(Some(i + 1))) {
+            return None;
+        }
+    }
+    Some(literal.replace('_', ""))
+}
+
+fn is_hex_digit(ch: char) -> bool {
+    ch.is_ascii_hexdigit()
 }
 
 fn map_token(string: String) -> TokenType {
     match string.as_str() {
         "and" => AND,
+        "break" => BREAK,
+        "catch" => CATCH,
         "class" => CLASS,
+        "const" => CONST,
+        "continue" => CONTINUE,
         "else" => ELSE,
+        "export" => EXPORT,
         "false" => FALSE,
+        "finally" => FINALLY,
         "for" => FOR,
         "fun" => FUN,
         "if" => IF,
+        "import" => IMPORT,
+        "is" => IS,
+        "match" => MATCH,
         "nil" => NIL,
         "or" => OR,
         "print" => PRINT,
         "return" => RETURN,
         "super" => SUPER,
         "this" => THIS,
+        "throw" => THROW,
         "true" => TRUE,
+        "try" => TRY,
         "var" => VAR,
         "while" => WHILE,
+        "yield" => YIELD,
         _ => IDENTIFIER(string),
     }
 }
@@ -1,4 +1,4 @@
-#![allow(non_camel_case_types)]
+#![allow(non_camel_case_types, clippy::upper_case_acronyms)]
 
 #[derive(Debug, PartialEq)]
 pub enum TokenType {
@@ -7,13 +7,22 @@ pub enum TokenType {
     RIGHT_PAREN,
     LEFT_BRACE,
     RIGHT_BRACE,
+    LEFT_BRACKET,
+    RIGHT_BRACKET,
     COMMA,
+    COLON,
+    QUESTION,
     DOT,
     HYPHEN,
     PLUS,
     SEMICOLON,
     SLASH,
     ASTERICS,
+    PERCENT,
+    AMPERSAND,
+    PIPE,
+    CARET,
+    TILDE,
 
     // One or two character tokens
     BANG,
@@ -24,44 +33,90 @@ pub enum TokenType {
     GREATER_EQUAL,
     LESS,
     LESS_EQUAL,
+    ASTERICS_ASTERICS,
+    PLUS_EQUAL,
+    HYPHEN_EQUAL,
+    ASTERICS_EQUAL,
+    SLASH_EQUAL,
+    PLUS_PLUS,
+    HYPHEN_HYPHEN,
+    TILDE_SLASH,
+    LESS_LESS,
+    GREATER_GREATER,
+    DOT_DOT,
+    ELLIPSIS,
+    ARROW,
+    THIN_ARROW,
 
     // Literals
     IDENTIFIER(String),
     STRING(String),
+    INTERPOLATION(String),
+    INTEGER(i64),
     NUMBER(f64),
 
     //Keywords
     AND,
+    BREAK,
+    CATCH,
     CLASS,
+    CONST,
+    CONTINUE,
     ELSE,
+    EXPORT,
     FALSE,
+    FINALLY,
     FUN,
     FOR,
     IF,
+    IMPORT,
+    IS,
+    MATCH,
     NIL,
     OR,
     PRINT,
     RETURN,
     SUPER,
     THIS,
+    THROW,
     TRUE,
+    TRY,
     VAR,
     WHILE,
+    YIELD,
 }
 
+/// A token, along with the text of any `///` doc comment right before it.
+///
+/// `offset` is the column of the token counted in chars, and `utf16` the same
+/// column counted in UTF-16 code units, as most editors do and as reported
+/// in diagnostics. Both start at 1. `byte` is the offset of the token from
+/// the start of the source in bytes.
 #[derive(Debug, PartialEq)]
 pub struct Token {
     pub token_type: TokenType,
     pub line: usize,
     pub offset: usize,
+    pub utf16: usize,
+    pub byte: usize,
+    pub doc: Option<String>,
 }
 
 impl Token {
-    pub fn new(token_type: TokenType, line: usize, offset: usize) -> Self {
+    pub fn new(
+        token_type: TokenType,
+        line: usize,
+        offset: usize,
+        utf16: usize,
+        byte: usize,
+    ) -> Self {
         Token {
-            token_type: token_type,
-            line: line,
-            offset: offset,
+            token_type,
+            line,
+            offset,
+            utf16,
+            byte,
+            doc: None,
         }
     }
 }
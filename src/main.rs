@@ -1,37 +1,31 @@
 extern crate lox;
 
-use lox::run;
+use lox::{check_file, run_file, Typing};
 use std::env;
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::Error;
 use std::process;
 
 fn main() {
-    let mut args = env::args();
-    let exec = args.next().unwrap();
-    match args.next() {
-        Some(path) => match args.next() {
-            None => {
-                println!("Running file: {}", path);
-                run_file(&path).unwrap();
+    let args: Vec<String> = env::args().collect();
+    let exec = &args[0];
+    let args: Vec<&str> = args[1..].iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["check", path] => {
+            println!("Checking file: {}", path);
+            if !check_file(path).unwrap() {
+                process::exit(65);
             }
-            Some(_) => {
-                println!("Usage: {} [script]", exec);
-                process::exit(64);
-            }
-        },
-        None => {
-            println!("Usage: {} [script]", exec);
+        }
+        ["--strict-types", path] => {
+            println!("Running file: {}", path);
+            run_file(path, Typing::Strict).unwrap();
+        }
+        [path] => {
+            println!("Running file: {}", path);
+            run_file(path, Typing::Dynamic).unwrap();
+        }
+        _ => {
+            println!("Usage: {} [check | --strict-types] [script]", exec);
             process::exit(64);
         }
     }
 }
-
-fn run_file(path: &str) -> Result<(), Error> {
-    let mut file = File::open(path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    run(&contents);
-    Ok(())
-}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn defaults_and_rest_parameters_fill_in_arguments() {
+    let output = run(
+        "defaults_and_rest_parameters_fill_in_arguments",
+        "
+        fun f(a, b = 2, ...rest) {
+          print a;
+          print b;
+          print rest;
+        }
+        f(1);
+        f(1, 3, 4, 5);
+        ",
+    );
+    assert_eq!(output.stdout, ["1", "2", "[]", "1", "3", "[4, 5]"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn wrong_number_of_arguments_is_an_error() {
+    let output = run("exact_arity", "fun g(a) {} g(1, 2);");
+    assert_eq!(output.stderr, ["g expects 1 argument but got 2"]);
+
+    let output = run("arity_with_defaults", "fun h(a, b = 1) {} h();");
+    assert_eq!(
+        output.stderr,
+        ["h expects between 1 and 2 arguments but got 0"]
+    );
+
+    let output = run("arity_with_rest", "fun f(a, ...rest) {} f();");
+    assert_eq!(output.stderr, ["f expects at least 1 argument but got 0"]);
+}
+
+#[test]
+fn class_arity_is_reported_against_the_class() {
+    let output = run(
+        "class_arity_is_reported_against_the_class",
+        "
+        class P { init(a, b) {} }
+        class R < P {}
+        class Q {}
+        try { P(1); } catch (e) { print e.message; }
+        try { R(); } catch (e) { print e.message; }
+        try { Q(1); } catch (e) { print e.message; }
+        ",
+    );
+    assert_eq!(
+        output.stdout,
+        [
+            "P expects 2 arguments but got 1",
+            "R expects 2 arguments but got 0",
+            "Q expects 0 arguments but got 1",
+        ]
+    );
+}
+
+#[test]
+fn parameter_without_default_after_one_with_default_is_an_error() {
+    let output = run(
+        "parameter_without_default_after_one_with_default",
+        "fun f(a = 1, b) {}",
+    );
+    assert_eq!(
+        output.stderr,
+        ["L1:14 Parameter 'b' must have a default value since it follows one"]
+    );
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn methods_read_and_write_fields() {
+    let output = run(
+        "methods_read_and_write_fields",
+        "
+        class Point {
+          init(x, y) { this.x = x; this.y = y; }
+          sum() { return this.x + this.y; }
+        }
+        var p = Point(1, 2);
+        print p.sum();
+        p.x = 10;
+        print p.sum();
+        var sum = p.sum;
+        print sum();
+        ",
+    );
+    assert_eq!(output.stdout, ["3", "12", "12"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn classes_and_instances_print_their_name() {
+    let output = run(
+        "classes_and_instances_print_their_name",
+        "
+        class Point {}
+        print Point;
+        print Point();
+        ",
+    );
+    assert_eq!(output.stdout, ["<class Point>", "<Point instance>"]);
+}
+
+#[test]
+fn missing_property_is_an_error() {
+    let output = run(
+        "missing_property_is_an_error",
+        "
+        class Point {}
+        print Point().z;
+        ",
+    );
+    assert_eq!(output.stderr, ["Undefined property 'z'"]);
+}
+
+#[test]
+fn only_instances_have_properties() {
+    let output = run("only_instances_have_properties", "print 1.x;");
+    assert_eq!(output.stderr, ["Only instances have properties, found 1"]);
+
+    let output = run("only_instances_have_fields", "var a = 1; a.x = 2;");
+    assert_eq!(output.stderr, ["Only instances have fields, found 1"]);
+}
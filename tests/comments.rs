@@ -0,0 +1,29 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn block_comments_nest_and_are_skipped() {
+    let output = run(
+        "block_comments_nest_and_are_skipped",
+        "
+        /* block /* nested */ still comment
+        */
+        /// doc comment
+        fun add(a, b) { return a + b; }
+        print add(1, 2) /* inline */ * 2;
+        var x = 6; x /= 4; print x;
+        // trailing comment without newline",
+    );
+    assert_eq!(output.stdout, ["6", "1.5"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn unterminated_block_comment_is_an_error() {
+    let output = run(
+        "unterminated_block_comment_is_an_error",
+        "print 1; /* never /* closed */",
+    );
+    assert_eq!(output.stderr, ["L1:10 Unterminated block comment"]);
+}
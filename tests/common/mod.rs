@@ -0,0 +1,57 @@
+//! Runs programs through the interpreter binary and collects what they
+//! print, so that tests can compare it line by line.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// What a program printed, without the banner naming the file.
+pub struct Output {
+    pub stdout: Vec<String>,
+    pub stderr: Vec<String>,
+}
+
+/// Runs a program with its annotations ignored.
+pub fn run(name: &str, code: &str) -> Output {
+    run_files(name, &[("main.lox", code)], &[])
+}
+
+/// Runs a program with its annotations checked and enforced at runtime.
+pub fn run_strict(name: &str, code: &str) -> Output {
+    run_files(name, &[("main.lox", code)], &["--strict-types"])
+}
+
+/// Type checks a program without running it.
+pub fn check(name: &str, code: &str) -> Output {
+    run_files(name, &[("main.lox", code)], &["check"])
+}
+
+/// Writes `files` to a directory of their own, named after the test, and
+/// runs the first of them with `args` before its path.
+pub fn run_files(name: &str, files: &[(&str, &str)], args: &[&str]) -> Output {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    for (file, code) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, code).unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_lox"))
+        .args(args)
+        .arg(dir.join(files[0].0))
+        .output()
+        .unwrap();
+    let lines = |bytes: &[u8], banner: usize| {
+        String::from_utf8_lossy(bytes)
+            .lines()
+            .skip(banner)
+            .map(str::to_string)
+            .collect()
+    };
+
+    Output {
+        stdout: lines(&output.stdout, 1),
+        stderr: lines(&output.stderr, 0),
+    }
+}
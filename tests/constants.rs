@@ -0,0 +1,73 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn constants_hold_their_value() {
+    let output = run(
+        "constants_hold_their_value",
+        "
+        const A = 1;
+        print A;
+        fun f(const x, y = 2) { y = 3; return x + y; }
+        print f(1);
+        {
+          const B = [1];
+          B[0] = 5;
+          print B;
+        }
+        ",
+    );
+    assert_eq!(output.stdout, ["1", "4", "[5]"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn assignments_to_constants_are_reported_before_running() {
+    let output = run("assignment_to_constant", "const Q = 1;\nQ = 2;");
+    assert_eq!(output.stderr, ["L2:1 Cannot assign to constant 'Q'"]);
+
+    let output = run("update_of_constant", "{ const a = 1; a++; }");
+    assert_eq!(output.stderr, ["L1:16 Cannot assign to constant 'a'"]);
+
+    let output = run(
+        "assignment_to_constant_parameter",
+        "fun f(const x) { x = 2; }",
+    );
+    assert_eq!(output.stderr, ["L1:18 Cannot assign to constant 'x'"]);
+}
+
+#[test]
+fn constants_cannot_be_redeclared() {
+    let output = run("constants_cannot_be_redeclared", "const Q = 1;\nvar Q = 2;");
+    assert_eq!(output.stderr, ["L2:5 Cannot redeclare constant 'Q'"]);
+}
+
+#[test]
+fn globals_assigned_before_their_declaration_are_checked_at_runtime() {
+    let output = run(
+        "globals_assigned_before_their_declaration_are_checked_at_runtime",
+        r#"
+        fun set() { A = 2; }
+        fun bump() { A += 1; }
+        const A = 1;
+        try { set(); } catch (e) { print e.kind + ": " + e.message; }
+        try { bump(); } catch (e) { print e.message; }
+        print A;
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        [
+            "TypeError: Cannot assign to constant 'A'",
+            "Cannot assign to constant 'A'",
+            "1",
+        ]
+    );
+}
+
+#[test]
+fn constants_need_a_value() {
+    let output = run("constants_need_a_value", "const a;");
+    assert_eq!(output.stderr, ["L1:8 Expect '=' after constant 'a'"]);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn declarations_unpack_lists_and_fields() {
+    let output = run(
+        "declarations_unpack_lists_and_fields",
+        r#"
+        var (a, b) = [1, 2];
+        print a + b;
+        var [x, ...rest] = [10, 20, 30];
+        print x;
+        print rest;
+        var {name, age} = {"name": "Ada", "age": 36};
+        print name;
+        print age;
+        class P { init() { this.px = 3; this.py = 4; } }
+        var {px, py} = P();
+        print px * py;
+        var [first, [second, third]] = [1, [2, 3]];
+        print first + second + third;
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        ["3", "10", "[20, 30]", "Ada", "36", "12", "6"]
+    );
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn multiple_assignment_swaps_and_unpacks() {
+    let output = run(
+        "multiple_assignment_swaps_and_unpacks",
+        r#"
+        var a = 1;
+        var b = 2;
+        a, b = b, a;
+        print a;
+        print b;
+        var list = [0, 0];
+        class P {}
+        var obj = P();
+        list[0], obj.px = "l", "o";
+        print list;
+        print obj.px;
+        a, b = [5, 6];
+        print a + b;
+        "#,
+    );
+    assert_eq!(output.stdout, ["2", "1", "[l, 0]", "o", "11"]);
+}
+
+#[test]
+fn values_that_do_not_fit_are_errors() {
+    let output = run("too_few_items", "var (a, b) = [1];");
+    assert_eq!(
+        output.stderr,
+        ["Cannot unpack [1] into [a, b], no value for b at position 2"]
+    );
+
+    let output = run("not_a_list", "var (a, b) = 5;");
+    assert_eq!(
+        output.stderr,
+        ["Cannot unpack 5 into [a, b], expected a list"]
+    );
+
+    let output = run("missing_field", r#"var {z} = {"a": 1};"#);
+    assert_eq!(
+        output.stderr,
+        ["Cannot unpack {a: 1} into {z}, no field 'z' at position 1"]
+    );
+}
+
+#[test]
+fn assignment_counts_must_match() {
+    let output = run(
+        "assignment_counts_must_match",
+        "var a = 1; var b = 2; a, b = 1, 2, 3;",
+    );
+    assert_eq!(output.stderr, ["L1:28 Cannot assign 3 values to 2 targets"]);
+}
+
+#[test]
+fn constant_targets_cannot_be_reassigned() {
+    let output = run(
+        "constant_targets_cannot_be_reassigned",
+        "{ const [c1, c2] = [7, 8]; c1 = 2; }",
+    );
+    assert_eq!(output.stderr, ["L1:28 Cannot assign to constant 'c1'"]);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn runtime_errors_are_caught_as_error_instances() {
+    let output = run(
+        "runtime_errors_are_caught_as_error_instances",
+        r#"
+        try { print 1 + "a"; } catch (e) { print e.kind; print e.message; }
+        try { print undefinedVar; } catch (e) { print e.kind + ": " + e.message; }
+        try { [1][3]; } catch (e) { print e.kind; }
+        try { 1 ~/ 0; } catch (e) { print e.kind; }
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        [
+            "TypeError",
+            "'+' operator is not defined for 1 and a",
+            "NameError: undefinedVar not defined",
+            "IndexError",
+            "ArithmeticError",
+        ]
+    );
+}
+
+#[test]
+fn thrown_values_are_caught_as_they_are() {
+    let output = run(
+        "thrown_values_are_caught_as_they_are",
+        r#"
+        try { throw "plain"; } catch (e) { print e; }
+        class MyError < Error { init(m) { super.init(m, "MyError"); } }
+        try { throw MyError("mine"); } catch (e) { print e.kind; print e.message; }
+        fun g() { throw Error("deep"); }
+        fun h() { try { g(); } catch (e) { return "caught " + e.message; } }
+        print h();
+        "#,
+    );
+    assert_eq!(output.stdout, ["plain", "MyError", "mine", "caught deep"]);
+}
+
+#[test]
+fn finally_runs_on_return_break_and_throw() {
+    let output = run(
+        "finally_runs_on_return_break_and_throw",
+        r#"
+        fun f() {
+          try { return "from try"; } finally { print "finally on return"; }
+        }
+        print f();
+        for (var i = 0; i < 3; i++) {
+          try { if (i == 1) break; print i; } finally { print "finally ${i}"; }
+        }
+        try {
+          try { throw 1; } finally { print "inner finally"; }
+        } catch (e) {
+          print "outer caught ${e}";
+        }
+        fun k() { try { throw 1; } catch (e) { return e + 1; } finally { print "k finally"; } }
+        print k();
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        [
+            "finally on return",
+            "from try",
+            "0",
+            "finally 0",
+            "finally 1",
+            "inner finally",
+            "outer caught 1",
+            "k finally",
+            "2",
+        ]
+    );
+}
+
+#[test]
+fn uncaught_errors_are_reported() {
+    let output = run("uncaught_errors_are_reported", r#"throw Error("bye");"#);
+    assert_eq!(output.stderr, ["Uncaught Error: bye"]);
+}
+
+#[test]
+fn caught_errors_use_the_prelude_error_class_when_shadowed() {
+    let output = run(
+        "caught_errors_use_the_prelude_error_class_when_shadowed",
+        r#"
+        class Error { init(msg) {} }
+        try { nil(); } catch (e) { print e.kind + ": " + e.message; }
+        {
+          var Error = nil;
+          try { [][3]; } catch (e) { print e.kind; }
+        }
+        fun f() { try { nil(); } catch (e) { return e.kind; } }
+        print f();
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        ["TypeError: nil is not callable", "IndexError", "TypeError"]
+    );
+}
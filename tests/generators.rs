@@ -0,0 +1,142 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn generators_yield_until_they_return() {
+    let output = run(
+        "generators_yield_until_they_return",
+        r#"
+        fun count(n) {
+          var i = 0;
+          while (i < n) {
+            yield i;
+            i = i + 1;
+          }
+          return "end";
+        }
+        var g = count(2);
+        print g;
+        print g.done;
+        print g.next();
+        print g.next();
+        print g.done;
+        print g.next();
+        print g.done;
+        print g.next();
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        [
+            "<generator count>",
+            "false",
+            "0",
+            "1",
+            "false",
+            "end",
+            "true",
+            "nil"
+        ]
+    );
+}
+
+#[test]
+fn yield_resumes_inside_nested_statements() {
+    let output = run(
+        "yield_resumes_inside_nested_statements",
+        r#"
+        fun nested() {
+          try {
+            yield "try";
+            throw Error("boom");
+          } catch (e) {
+            yield "caught " + e.message;
+          } finally {
+            yield "finally";
+          }
+          match (2) {
+            n if n > 1 => { yield "many ${n}"; yield "again"; }
+          }
+          for (var i = 1; ; i = i + 1) {
+            if (i % 2 == 0) continue;
+            if (i > 3) break;
+            yield i;
+          }
+        }
+        var n = nested();
+        while (!n.done) print n.next();
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        [
+            "try",
+            "caught boom",
+            "finally",
+            "many 2",
+            "again",
+            "1",
+            "3",
+            "nil"
+        ]
+    );
+}
+
+#[test]
+fn methods_and_lambdas_can_be_generators() {
+    let output = run(
+        "methods_and_lambdas_can_be_generators",
+        "
+        class Tree {
+          init(items) { this.items = items; }
+          walk() {
+            for (var i = 0; i < len(this.items); i++) yield this.items[i];
+          }
+        }
+        var w = Tree([7, 8]).walk();
+        print w.next();
+        print w.next();
+        var lam = fun () { yield 5; };
+        print lam().next();
+        ",
+    );
+    assert_eq!(output.stdout, ["7", "8", "5"]);
+}
+
+#[test]
+fn errors_thrown_inside_finish_the_generator() {
+    let output = run(
+        "errors_thrown_inside_finish_the_generator",
+        r#"
+        fun fails() {
+          yield 1;
+          throw Error("bad");
+        }
+        var f = fails();
+        print f.next();
+        try { f.next(); } catch (e) { print e.message; }
+        print f.done;
+        "#,
+    );
+    assert_eq!(output.stdout, ["1", "bad", "true"]);
+}
+
+#[test]
+fn running_generator_cannot_be_resumed() {
+    let output = run(
+        "running_generator_cannot_be_resumed",
+        "
+        fun self_ref() { yield s.next(); }
+        var s = self_ref();
+        s.next();
+        ",
+    );
+    assert_eq!(output.stderr, ["Generator self_ref is already running"]);
+}
+
+#[test]
+fn yield_outside_of_a_function_is_an_error() {
+    let output = run("yield_outside_of_a_function_is_an_error", "yield 1;");
+    assert_eq!(output.stderr, ["L1:1 Cannot yield from top-level code"]);
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn methods_are_inherited_and_overridden() {
+    let output = run(
+        "methods_are_inherited_and_overridden",
+        r#"
+        class A {
+          init(a) { this.a = a; }
+          hi() { return "A"; }
+          name() { return "a"; }
+        }
+        class B < A {
+          hi() { return "B" + super.hi(); }
+        }
+        var b = B(3);
+        print b.hi();
+        print b.name();
+        print b.a;
+        "#,
+    );
+    assert_eq!(output.stdout, ["BA", "a", "3"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn superclass_must_be_a_class() {
+    let output = run(
+        "superclass_must_be_a_class",
+        "
+        var x = 1;
+        class B < x {}
+        ",
+    );
+    assert_eq!(output.stderr, ["Superclass of B must be a class, found 1"]);
+}
+
+#[test]
+fn class_cannot_inherit_from_itself() {
+    let output = run("class_cannot_inherit_from_itself", "class A < A {}");
+    assert_eq!(output.stderr, ["L1:11 A class can't inherit from itself"]);
+}
+
+#[test]
+fn super_needs_a_superclass() {
+    let output = run(
+        "super_needs_a_superclass",
+        "class A { m() { return super.m(); } }",
+    );
+    assert_eq!(
+        output.stderr,
+        ["L1:24 Cannot use 'super' in a class with no superclass"]
+    );
+
+    let output = run("super_outside_of_a_class", "print super.x;");
+    assert_eq!(
+        output.stderr,
+        ["L1:7 Cannot use 'super' outside of a class"]
+    );
+}
+
+#[test]
+fn missing_superclass_method_is_an_error() {
+    let output = run(
+        "missing_superclass_method_is_an_error",
+        "
+        class A {}
+        class B < A { m() { return super.m(); } }
+        B().m();
+        ",
+    );
+    assert_eq!(output.stderr, ["Undefined superclass method 'm'"]);
+}
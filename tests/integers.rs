@@ -0,0 +1,60 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn integers_stay_integers_until_mixed_with_floats() {
+    let output = run(
+        "integers_stay_integers_until_mixed_with_floats",
+        "
+        print 1 + 2;
+        print 1 + 2.0;
+        print 7 / 2;
+        print 6 / 2;
+        print 1 == 1.0;
+        ",
+    );
+    assert_eq!(output.stdout, ["3", "3.0", "3.5", "3.0", "true"]);
+}
+
+#[test]
+fn overflow_is_an_error() {
+    let output = run("addition_overflow", "print 9223372036854775807 + 1;");
+    assert_eq!(
+        output.stderr,
+        ["Integer overflow in 9223372036854775807 + 1"]
+    );
+
+    let output = run("multiplication_overflow", "print 4611686018427387904 * 2;");
+    assert_eq!(
+        output.stderr,
+        ["Integer overflow in 4611686018427387904 * 2"]
+    );
+
+    let output = run("power_overflow", "print 2 ** 63;");
+    assert_eq!(output.stderr, ["Integer overflow in 2 ** 63"]);
+}
+
+#[test]
+fn negation_overflow_parenthesizes_the_operand() {
+    let output = run(
+        "negation_overflow_parenthesizes_the_operand",
+        "print -(-9223372036854775807 - 1);",
+    );
+    assert_eq!(
+        output.stderr,
+        ["Integer overflow in -(-9223372036854775808)"]
+    );
+}
+
+#[test]
+fn literal_too_large_is_an_error() {
+    let output = run(
+        "literal_too_large_is_an_error",
+        "print 9223372036854775808;",
+    );
+    assert_eq!(
+        output.stderr,
+        ["L1:7 Integer literal 9223372036854775808 is too large"]
+    );
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn builtin_values_are_iterable() {
+    let output = run(
+        "builtin_values_are_iterable",
+        r#"
+        for (var c in "hé") print c;
+        for (var i in range(5, 0, -2)) print i;
+        for (var x in [1, 2, 3]) { if (x == 2) continue; print x; }
+        for (var k in {"b": 1, "a": 2}) print k;
+        fun squares(n) { for (var i in range(n)) yield i * i; }
+        for (var s in squares(3)) print s;
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        ["h", "é", "5", "3", "1", "1", "3", "a", "b", "0", "1", "4"]
+    );
+}
+
+#[test]
+fn each_iteration_has_its_own_variable() {
+    let output = run(
+        "each_iteration_has_its_own_variable",
+        "
+        var fns = [];
+        for (var i in range(3)) push(fns, () => i);
+        for (var f in fns) print f();
+        ",
+    );
+    assert_eq!(output.stdout, ["0", "1", "2"]);
+}
+
+#[test]
+fn instances_with_next_and_done_are_iterators() {
+    let output = run(
+        "instances_with_next_and_done_are_iterators",
+        "
+        class Countdown {
+          init(n) { this.n = n; this.done = false; }
+          next() {
+            if (this.n == 0) { this.done = true; return nil; }
+            this.n = this.n - 1;
+            return this.n + 1;
+          }
+        }
+        for (var c in Countdown(3)) print c;
+        ",
+    );
+    assert_eq!(output.stdout, ["3", "2", "1"]);
+}
+
+#[test]
+fn instances_with_iter_are_iterable() {
+    let output = run(
+        "instances_with_iter_are_iterable",
+        r#"
+        class Bag {
+          init() { this.items = ["x", "y"]; }
+          iter() { return this.items; }
+        }
+        for (var item in Bag()) print item;
+        "#,
+    );
+    assert_eq!(output.stdout, ["x", "y"]);
+}
+
+#[test]
+fn values_without_the_protocol_are_errors() {
+    let output = run("number_is_not_iterable", "for (var x in 5) print x;");
+    assert_eq!(output.stderr, ["5 is not iterable"]);
+
+    let output = run(
+        "instance_without_methods_is_not_iterable",
+        "class E {} for (var x in E()) print x;",
+    );
+    assert_eq!(
+        output.stderr,
+        ["<E instance> is not iterable, it has neither an 'iter' nor a 'next' method"]
+    );
+
+    let output = run(
+        "iterator_without_done",
+        "class NoDone { next() { return 1; } } for (var x in NoDone()) print x;",
+    );
+    assert_eq!(
+        output.stderr,
+        ["<NoDone instance> is not an iterator, it has a 'next' method but no 'done' field"]
+    );
+}
+
+#[test]
+fn iter_cycles_are_errors() {
+    let output = run(
+        "iter_cycles_are_errors",
+        "
+        class A { iter() { return B(this); } }
+        class B { init(a) { this.a = a; } iter() { return this.a; } }
+        for (var x in A()) print x;
+        ",
+    );
+    assert_eq!(
+        output.stderr,
+        ["<A instance> is not iterable, its 'iter' method leads back to <A instance> without reaching a 'next' method"]
+    );
+}
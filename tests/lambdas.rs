@@ -0,0 +1,53 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn anonymous_functions_are_values() {
+    let output = run(
+        "anonymous_functions_are_values",
+        "
+        var add = fun (a, b) { return a + b; };
+        print add(1, 2);
+        print fun () {};
+        ",
+    );
+    assert_eq!(output.stdout, ["3", "<function lambda>"]);
+}
+
+#[test]
+fn arrow_functions_return_their_body() {
+    let output = run(
+        "arrow_functions_return_their_body",
+        "
+        var square = (x) => x * x;
+        print square(4);
+        var seven = () => 7;
+        print seven();
+        var add = (a, b = 10) => a + b;
+        print add(1);
+        fun twice(f, x) { return f(f(x)); }
+        print twice((x) => x + 1, 0);
+        ",
+    );
+    assert_eq!(output.stdout, ["16", "7", "11", "2"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn arrow_parameter_without_default_after_one_with_default_is_an_error() {
+    let output = run(
+        "arrow_parameter_without_default_after_one_with_default",
+        "var f = (a = 1, b) => a;",
+    );
+    assert_eq!(
+        output.stderr,
+        ["L1:17 Parameter 'b' must have a default value since it follows one"]
+    );
+}
+
+#[test]
+fn arrow_parameters_must_be_names() {
+    let output = run("arrow_parameters_must_be_names", "var f = (1) => 2;");
+    assert_eq!(output.stderr, ["Invalid arrow function parameter"]);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn lists_are_indexed_and_mutated() {
+    let output = run(
+        "lists_are_indexed_and_mutated",
+        "
+        var l = [1, 2, 3];
+        print l[0];
+        l[1] = 5;
+        print l;
+        push(l, 4);
+        print len(l);
+        print pop(l);
+        insert(l, 0, 9);
+        print l;
+        print remove(l, 0);
+        print [l, []];
+        ",
+    );
+    assert_eq!(
+        output.stdout,
+        [
+            "1",
+            "[1, 5, 3]",
+            "4",
+            "4",
+            "[9, 1, 5, 3]",
+            "9",
+            "[[1, 5, 3], []]"
+        ]
+    );
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn list_containing_itself_prints() {
+    let output = run(
+        "list_containing_itself_prints",
+        "var a = [1]; push(a, a); print a;",
+    );
+    assert_eq!(output.stdout, ["[1, [...]]"]);
+}
+
+#[test]
+fn invalid_indexes_are_errors() {
+    let output = run("index_out_of_bounds", "print [1][3];");
+    assert_eq!(
+        output.stderr,
+        ["Index 3 out of bounds for list of length 1"]
+    );
+
+    let output = run("index_not_an_integer", r#"print [1]["a"];"#);
+    assert_eq!(output.stderr, ["List index must be an integer, found a"]);
+
+    let output = run("index_not_a_list", "print 1[0];");
+    assert_eq!(output.stderr, ["1 is not indexable"]);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn break_and_continue_leave_the_innermost_loop() {
+    let output = run(
+        "break_and_continue_leave_the_innermost_loop",
+        "
+        for (var i = 0; i < 5; i = i + 1) {
+          if (i == 1) continue;
+          if (i == 3) break;
+          print i;
+        }
+        var j = 0;
+        while (true) {
+          j = j + 1;
+          if (j > 2) break;
+        }
+        print j;
+        ",
+    );
+    assert_eq!(output.stdout, ["0", "2", "3"]);
+}
+
+#[test]
+fn bare_return_returns_nil() {
+    let output = run("bare_return_returns_nil", "fun f() { return; } print f();");
+    assert_eq!(output.stdout, ["nil"]);
+}
+
+#[test]
+fn break_and_continue_must_be_in_a_loop() {
+    let output = run("break_outside_of_a_loop", "break;");
+    assert_eq!(output.stderr, ["L1:1 Cannot use 'break' outside of a loop"]);
+
+    let output = run("continue_outside_of_a_loop", "fun f() { continue; }");
+    assert_eq!(
+        output.stderr,
+        ["L1:11 Cannot use 'continue' outside of a loop"]
+    );
+
+    let output = run(
+        "break_in_a_function_in_a_loop",
+        "while (true) { fun g() { break; } break; }",
+    );
+    assert_eq!(
+        output.stderr,
+        ["L1:26 Cannot use 'break' outside of a loop"]
+    );
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn maps_are_keyed_by_strings_numbers_and_booleans() {
+    let output = run(
+        "maps_are_keyed_by_strings_numbers_and_booleans",
+        r#"
+        var m = {"a": 1, 2: "two", true: [1]};
+        print m["a"];
+        print m[2];
+        print m[true];
+        print m[2.0];
+        m["b"] = 3;
+        print len(m);
+        print has(m, "b");
+        print delete(m, "b");
+        print has(m, "b");
+        print {};
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        ["1", "two", "[1]", "two", "4", "true", "3", "false", "{}"]
+    );
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn missing_key_is_an_error() {
+    let output = run("missing_key_is_an_error", r#"print {"a": 1}["z"];"#);
+    assert_eq!(output.stderr, ["Key 'z' not found in map"]);
+}
+
+#[test]
+fn lists_cannot_be_keys() {
+    let output = run("lists_cannot_be_keys", "var m = {}; m[[1]] = 2;");
+    assert_eq!(output.stderr, ["[1] cannot be used as a map key"]);
+}
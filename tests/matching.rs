@@ -0,0 +1,96 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn arms_match_literals_ranges_types_and_guards() {
+    let output = run(
+        "arms_match_literals_ranges_types_and_guards",
+        r#"
+        fun describe(v) {
+          match (v) {
+            1 => return "one";
+            "a" | "b" => return "a or b";
+            -5..0 => return "negative small";
+            0..10 => return "small";
+            is string => return "some string";
+            is list => return "list";
+            x if x != nil and x > 100 => return "big ${x}";
+            _ => return "other";
+          }
+        }
+        print describe(1);
+        print describe(1.0);
+        print describe("b");
+        print describe(-3);
+        print describe(9.5);
+        print describe(10);
+        print describe("zzz");
+        print describe(500);
+        print describe([1]);
+        print describe(nil);
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        [
+            "one",
+            "one",
+            "a or b",
+            "negative small",
+            "small",
+            "other",
+            "some string",
+            "big 500",
+            "list",
+            "other",
+        ]
+    );
+}
+
+#[test]
+fn bindings_are_scoped_to_their_arm() {
+    let output = run(
+        "bindings_are_scoped_to_their_arm",
+        r#"
+        var x = "outer";
+        match (42) {
+          x => print x;
+        }
+        print x;
+        "#,
+    );
+    assert_eq!(output.stdout, ["42", "outer"]);
+}
+
+#[test]
+fn break_and_continue_apply_to_the_enclosing_loop() {
+    let output = run(
+        "break_and_continue_apply_to_the_enclosing_loop",
+        "
+        for (var i = 0; i < 5; i++) {
+          match (i) {
+            2 => continue;
+            4 => break;
+            _ => print i;
+          }
+        }
+        ",
+    );
+    assert_eq!(output.stdout, ["0", "1", "3"]);
+}
+
+#[test]
+fn no_matching_arm_is_an_error() {
+    let output = run("no_matching_arm_is_an_error", "match (5) { 1 => print 1; }");
+    assert_eq!(output.stderr, ["No match arm matches 5 of type int"]);
+}
+
+#[test]
+fn unknown_type_pattern_is_an_error() {
+    let output = run(
+        "unknown_type_pattern_is_an_error",
+        "match (5) { is foo => print 1; }",
+    );
+    assert!(output.stderr[0].starts_with("L1:13 Unknown type 'foo', expected one of int, float"));
+}
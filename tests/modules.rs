@@ -0,0 +1,111 @@
+mod common;
+
+use common::run_files;
+
+const UTIL: &str = r#"
+print "loading util";
+export fun double(x) { return x * 2; }
+export var name = "util";
+var hidden = 1;
+export class Point { init(x) { this.x = x; } }
+import "helper.lox" as h;
+export fun help() { return h.greet(); }
+"#;
+
+const HELPER: &str = r#"export fun greet() { return "hi from helper"; }"#;
+
+#[test]
+fn exports_are_imported_by_module_or_by_name() {
+    let output = run_files(
+        "exports_are_imported_by_module_or_by_name",
+        &[
+            (
+                "main.lox",
+                r#"
+                import "lib/util.lox" as u;
+                import { double, name } from "lib/util.lox";
+                print u.double(4);
+                print double(5);
+                print name;
+                print u.Point(3).x;
+                print u.help();
+                "#,
+            ),
+            ("lib/util.lox", UTIL),
+            ("lib/helper.lox", HELPER),
+        ],
+        &[],
+    );
+    // a module runs once however many times it is imported, and its own
+    // imports are relative to it
+    assert_eq!(
+        output.stdout,
+        ["loading util", "8", "10", "util", "3", "hi from helper"]
+    );
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn names_not_exported_are_errors() {
+    let output = run_files(
+        "names_not_exported_are_errors",
+        &[
+            (
+                "main.lox",
+                r#"
+                import "lib/util.lox" as u;
+                try { print u.hidden; } catch (e) { print e.kind; }
+                import { nope } from "lib/util.lox";
+                "#,
+            ),
+            ("lib/util.lox", UTIL),
+            ("lib/helper.lox", HELPER),
+        ],
+        &[],
+    );
+    assert_eq!(output.stdout, ["loading util", "NameError"]);
+    assert_eq!(output.stderr.len(), 1);
+    assert!(output.stderr[0].ends_with("lib/util.lox' has no export 'nope'"));
+}
+
+#[test]
+fn missing_modules_are_import_errors() {
+    let output = run_files(
+        "missing_modules_are_import_errors",
+        &[(
+            "main.lox",
+            r#"try { import "missing.lox" as m; } catch (e) { print e.kind; }"#,
+        )],
+        &[],
+    );
+    assert_eq!(output.stdout, ["ImportError"]);
+}
+
+#[test]
+fn import_cycles_are_errors() {
+    let output = run_files(
+        "import_cycles_are_errors",
+        &[
+            ("a.lox", r#"import "b.lox" as b;"#),
+            ("b.lox", r#"import "a.lox" as a;"#),
+        ],
+        &[],
+    );
+    assert_eq!(output.stderr.len(), 1);
+    assert!(output.stderr[0].starts_with("Error in module '"));
+    assert!(output.stderr[0].ends_with("a.lox'"));
+    assert!(output.stderr[0].contains("b.lox': Import cycle through '"));
+}
+
+#[test]
+fn only_top_level_declarations_are_exported() {
+    let output = run_files(
+        "only_top_level_declarations_are_exported",
+        &[("main.lox", "{ export var x = 1; }")],
+        &[],
+    );
+    assert_eq!(
+        output.stderr,
+        ["L1:14 Can only export top-level declarations"]
+    );
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn clock_returns_increasing_seconds() {
+    let output = run(
+        "clock_returns_increasing_seconds",
+        "
+        print clock;
+        var start = clock();
+        print start > 0;
+        print clock() >= start;
+        ",
+    );
+    assert_eq!(output.stdout, ["<native function clock>", "true", "true"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn native_arity_is_checked() {
+    let output = run("native_arity_is_checked", "clock(1);");
+    assert_eq!(output.stderr, ["clock expects 0 arguments but got 1"]);
+}
+
+#[test]
+fn native_errors_are_reported() {
+    let output = run("native_errors_are_reported", "print len(1);");
+    assert_eq!(output.stderr, ["len() is not defined for 1"]);
+}
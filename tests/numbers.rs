@@ -0,0 +1,79 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn literals_in_every_base_and_with_separators() {
+    let output = run(
+        "literals_in_every_base_and_with_separators",
+        "
+        print 0xFF;
+        print 0b1010;
+        print 0o17;
+        print 0XfF + 1;
+        print 1_000_000;
+        print 0x7FFF_FFFF_FFFF_FFFF;
+        ",
+    );
+    assert_eq!(
+        output.stdout,
+        ["255", "10", "15", "256", "1000000", "9223372036854775807"]
+    );
+}
+
+#[test]
+fn literals_with_exponents_are_floats() {
+    let output = run(
+        "literals_with_exponents_are_floats",
+        "
+        print 1e-9;
+        print 6.02E23;
+        print 1_000.5e1;
+        print 2e3;
+        ",
+    );
+    assert_eq!(output.stdout, ["1e-9", "6.02e23", "10005.0", "2000.0"]);
+}
+
+#[test]
+fn malformed_literals_are_errors() {
+    let output = run(
+        "malformed_literals_are_errors",
+        "print 0x;
+print 1e+;
+print 0b102;
+print 1__0;
+print 1_;
+print 0x_1;
+",
+    );
+    assert_eq!(
+        output.stderr,
+        [
+            "L1:7 Expected digits after '0x'",
+            "L2:7 Expected digits in the exponent of 1e+",
+            "L3:7 Invalid digit '2' in number 0b102",
+            "L4:7 Misplaced '_' in number 1__0",
+            "L5:7 Misplaced '_' in number 1_",
+            "L6:7 Misplaced '_' in number 0x_1",
+        ]
+    );
+}
+
+#[test]
+fn literals_too_large_are_errors() {
+    let output = run(
+        "literals_too_large_are_errors",
+        "print 0x8000000000000000;
+print 1e400;
+print 1.5e308;
+",
+    );
+    assert_eq!(
+        output.stderr,
+        [
+            "L1:7 Integer literal 0x8000000000000000 is too large",
+            "L2:7 Float literal 1e400 is too large",
+        ]
+    );
+}
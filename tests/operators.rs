@@ -0,0 +1,53 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn arithmetic_operators() {
+    let output = run(
+        "arithmetic_operators",
+        "
+        print 7 % 3;
+        print -7 % 3;
+        print 7.5 % 2;
+        print 2 ** 10;
+        print 2 ** -1;
+        print 2 ** 3 ** 2;
+        ",
+    );
+    assert_eq!(output.stdout, ["1", "-1", "1.5", "1024", "0.5", "512"]);
+}
+
+#[test]
+fn bitwise_operators() {
+    let output = run(
+        "bitwise_operators",
+        "
+        print 6 & 3;
+        print 6 | 3;
+        print 6 ^ 3;
+        print ~5;
+        print 1 << 4;
+        print -16 >> 2;
+        ",
+    );
+    assert_eq!(output.stdout, ["2", "7", "5", "-6", "16", "-4"]);
+}
+
+#[test]
+fn invalid_operands_are_errors() {
+    let output = run("modulo_by_zero", "print 1 % 0;");
+    assert_eq!(output.stderr, ["Division by zero in 1 % 0"]);
+
+    let output = run("bitwise_on_float", "print 1.5 & 1;");
+    assert_eq!(
+        output.stderr,
+        ["Operator '&' expects integer operands, found 1.5"]
+    );
+
+    let output = run("shift_too_far", "print 1 << 64;");
+    assert_eq!(
+        output.stderr,
+        ["Shift amount for '<<' must be between 0 and 63, found 64"]
+    );
+}
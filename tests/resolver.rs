@@ -0,0 +1,63 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn closures_bind_the_variable_in_scope_where_declared() {
+    let output = run(
+        "closures_bind_the_variable_in_scope_where_declared",
+        r#"
+        var a = "global";
+        {
+          fun show() { print a; }
+          show();
+          var a = "block";
+          show();
+          print a;
+        }
+        "#,
+    );
+    assert_eq!(output.stdout, ["global", "global", "block"]);
+}
+
+#[test]
+fn closures_see_later_assignments() {
+    let output = run(
+        "closures_see_later_assignments",
+        "
+        fun f() {
+          var a = 1;
+          fun g() { return a; }
+          a = 2;
+          return g;
+        }
+        print f()();
+        ",
+    );
+    assert_eq!(output.stdout, ["2"]);
+}
+
+#[test]
+fn errors_are_reported_with_their_position() {
+    let output = run(
+        "errors_are_reported_with_their_position",
+        "{ var a = a; }
+{ var b = 1; var b = 2; }
+return 1;
+print this;
+fun f(p, p) {}
+while (true) {} break;
+",
+    );
+    assert_eq!(
+        output.stderr,
+        [
+            "L1:11 Cannot read local variable 'a' in its own initializer",
+            "L2:18 Variable 'b' is already declared in this scope",
+            "L3:1 Cannot return from top-level code",
+            "L4:7 Cannot use 'this' outside of a class",
+            "L5:5 Variable 'p' is already declared in this scope",
+            "L6:17 Cannot use 'break' outside of a loop",
+        ]
+    );
+}
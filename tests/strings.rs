@@ -0,0 +1,49 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn escape_sequences_are_decoded() {
+    let output = run(
+        "escape_sequences_are_decoded",
+        r#"
+        print "a\tb\\c\"d\u{e9}";
+        print "a\nb";
+        "#,
+    );
+    assert_eq!(output.stdout, ["a\tb\\c\"dé", "a", "b"]);
+}
+
+#[test]
+fn interpolation_evaluates_expressions() {
+    let output = run(
+        "interpolation_evaluates_expressions",
+        r#"
+        var x = 2;
+        print "x = ${x + 1}!";
+        print "${"nested ${x}"}";
+        "#,
+    );
+    assert_eq!(output.stdout, ["x = 3!", "nested 2"]);
+}
+
+#[test]
+fn invalid_escapes_are_errors() {
+    let output = run("invalid_escape", r#"print "\q";"#);
+    assert_eq!(output.stderr, ["L1:8 Invalid escape sequence '\\q'"]);
+
+    let output = run("invalid_unicode_escape", r#"print "\u{110000}";"#);
+    assert_eq!(output.stderr, ["L1:8 Invalid unicode escape sequence"]);
+}
+
+#[test]
+fn unterminated_interpolation_is_an_error() {
+    let output = run("unterminated_interpolation", r#"print "${x";"#);
+    assert_eq!(
+        output.stderr,
+        [
+            "L1:11 Unterminated string.",
+            "Unterminated string interpolation"
+        ]
+    );
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn ternary_picks_a_branch_by_truthiness() {
+    let output = run(
+        "ternary_picks_a_branch_by_truthiness",
+        r#"
+        print true ? 1 : 2;
+        print nil ? 1 : 2;
+        print 0 ? "zero" : "none";
+        print false ? 1 : true ? 3 : 4;
+        "#,
+    );
+    assert_eq!(output.stdout, ["1", "2", "zero", "3"]);
+}
+
+#[test]
+fn only_the_chosen_branch_is_evaluated() {
+    let output = run(
+        "only_the_chosen_branch_is_evaluated",
+        r#"
+        fun f() { print "called"; return 1; }
+        print true ? 5 : f();
+        "#,
+    );
+    assert_eq!(output.stdout, ["5"]);
+}
+
+#[test]
+fn missing_colon_is_an_error() {
+    let output = run("missing_colon_is_an_error", "print true ? 1;");
+    assert_eq!(output.stderr, ["L1:15 Expect ':' after then branch of '?'"]);
+}
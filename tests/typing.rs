@@ -0,0 +1,210 @@
+mod common;
+
+use common::{check, run, run_strict};
+
+#[test]
+fn annotated_values_are_checked() {
+    let output = check(
+        "annotated_values_are_checked",
+        r#"var a: number = "one";
+var b: string;
+var d: int = 1;
+d = 2.5;
+d += 0.5;
+fun f(s: string, n: number?) -> bool { return "yes"; }
+f(1, nil);
+var r: string = f("a", 1);
+"#,
+    );
+    assert_eq!(
+        output.stderr,
+        [
+            "L1:5 Variable 'a' expects number, found string",
+            "L2:5 Variable 'b' expects string, found nil",
+            "L4:1 Variable 'd' expects int, found float",
+            "L5:1 Variable 'd' expects int, found float",
+            "L6:40 Return value of f expects bool, found string",
+            "L7:2 Parameter 's' of f expects string, found int",
+            "L8:5 Variable 'r' expects string, found bool",
+        ]
+    );
+}
+
+#[test]
+fn classes_match_their_subclasses() {
+    let output = check(
+        "classes_match_their_subclasses",
+        "class Animal {}
+class Dog < Animal {}
+class Car {}
+fun pet(animal: Animal) {}
+pet(Dog());
+pet(Car());
+",
+    );
+    assert_eq!(
+        output.stderr,
+        ["L6:4 Parameter 'animal' of pet expects Animal, found Car"]
+    );
+}
+
+#[test]
+fn possibly_nil_values_are_narrowed_by_comparisons() {
+    let output = check(
+        "possibly_nil_values_are_narrowed_by_comparisons",
+        r#"var c: string? = nil;
+c + "x";
+if (c != nil) print c + "x";
+fun early(v: int?) -> int {
+  if (v == nil) return 0;
+  return v * 2;
+}
+"#,
+    );
+    assert_eq!(
+        output.stderr,
+        ["L2:1 Cannot apply '+' to 'c', which is possibly nil"]
+    );
+}
+
+#[test]
+fn operators_and_loops_are_checked() {
+    let output = check(
+        "operators_and_loops_are_checked",
+        r#"-"s";
+1 - "s";
+for (var i in 3) print i;
+"#,
+    );
+    assert_eq!(
+        output.stderr,
+        [
+            "L1:1 '-' operator is not defined for string",
+            "L2:3 '-' operator is not defined for int and string",
+            "L3:15 Cannot iterate over a value of type int",
+        ]
+    );
+}
+
+#[test]
+fn unknown_types_and_missing_returns_are_errors() {
+    let output = check(
+        "unknown_types_and_missing_returns_are_errors",
+        "var z: nmber = 1;
+fun h() -> int {}
+fun k(x: int) -> int { if (x > 0) return 1; else return 2; }
+fun n() -> int? {}
+fun make() -> Point { return Point(); }
+class Point {}
+",
+    );
+    assert_eq!(
+        output.stderr,
+        [
+            "L2:5 Function 'h' returns int, but can reach the end of its body without returning",
+            "L1:5 Unknown type 'nmber'",
+        ]
+    );
+}
+
+#[test]
+fn annotations_are_ignored_unless_checked() {
+    let output = run(
+        "annotations_are_ignored_unless_checked",
+        r#"var a: int = "one"; print a;"#,
+    );
+    assert_eq!(output.stdout, ["one"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn strict_mode_checks_values_at_runtime() {
+    let output = run_strict(
+        "strict_mode_checks_values_at_runtime",
+        r#"
+        fun id(v) { return v; }
+        fun half(n: number) -> float { return n / 2; }
+        fun gen() -> int { yield 1; yield id("two"); }
+        print half(3);
+        try { half(id("3")); } catch (e) { print e.message; }
+        var g = gen();
+        print g.next();
+        try { g.next(); } catch (e) { print e.message; }
+        var x: int = 1;
+        try { x = id(1.5); } catch (e) { print e.message; }
+        print x;
+        var z: string? = id(nil);
+        print z;
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        [
+            "1.5",
+            "Parameter 'n' of half expects number, found 3 of type string",
+            "1",
+            "Yielded value of gen expects int, found two of type string",
+            "Variable 'x' expects int, found 1.5 of type float",
+            "1",
+            "nil",
+        ]
+    );
+}
+
+#[test]
+fn strict_mode_checks_compound_assignments() {
+    let output = run_strict(
+        "strict_mode_checks_compound_assignments",
+        "
+        var x: int = 1;
+        fun f() { return 0.5; }
+        try { x += f(); } catch (e) { print e.message; }
+        print x;
+        x += 2;
+        x++;
+        print x;
+        ",
+    );
+    assert_eq!(
+        output.stdout,
+        [
+            "Variable 'x' expects int, found 1.5 of type float",
+            "1",
+            "4"
+        ]
+    );
+}
+
+#[test]
+fn strict_mode_checks_unpacked_values() {
+    let output = run_strict(
+        "strict_mode_checks_unpacked_values",
+        r#"
+        var a: int = 1;
+        var b: int = 2;
+        fun pair() { return ["s", "t"]; }
+        try { a, b = pair(); } catch (e) { print e.message; }
+        print a;
+        var c: string? = nil;
+        fun pair2() { return [nil, 5]; }
+        c, a = pair2();
+        print a;
+        "#,
+    );
+    assert_eq!(
+        output.stdout,
+        ["Variable 'a' expects int, found s of type string", "1", "5"]
+    );
+
+    let output = check(
+        "unpacked_list_literals_are_checked",
+        r#"var a: int = 1;
+var b: int = 2;
+a, b = ["s", 3];
+"#,
+    );
+    assert_eq!(
+        output.stderr,
+        ["L3:1 Variable 'a' expects int, found string"]
+    );
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn identifiers_can_be_unicode() {
+    let output = run(
+        "identifiers_can_be_unicode",
+        r#"
+        var café = 1;
+        var π = 3;
+        var 名前 = "x";
+        print café + π;
+        print 名前;
+        "#,
+    );
+    assert_eq!(output.stdout, ["4", "x"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn characters_outside_identifiers_are_errors() {
+    let output = run("emoji_identifier", "var 😀 = 1;");
+    assert_eq!(
+        output.stderr,
+        [
+            "L1:5 Unknown token: '😀'",
+            "L1:8 Expected IDENTIFIER, found EQUAL"
+        ]
+    );
+
+    let output = run("superscript_in_identifier", "var x² = 3;");
+    assert_eq!(output.stderr, ["L1:6 Unknown token: '²'"]);
+}
+
+#[test]
+fn columns_count_utf16_code_units() {
+    let output = run(
+        "parser_columns_count_utf16_code_units",
+        r#"var s = "😀"; var 1 = 2;"#,
+    );
+    assert_eq!(
+        output.stderr,
+        ["L1:19 Expected IDENTIFIER, found INTEGER(1)"]
+    );
+
+    let output = run(
+        "scanner_columns_count_utf16_code_units",
+        "var s = \"😀\"; var t = 0x;\nvar é = 1; ☃",
+    );
+    assert_eq!(
+        output.stderr,
+        [
+            "L1:23 Expected digits after '0x'",
+            "L2:12 Unknown token: '☃'"
+        ]
+    );
+}
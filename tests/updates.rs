@@ -0,0 +1,74 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn compound_assignment_updates_variables() {
+    let output = run(
+        "compound_assignment_updates_variables",
+        r#"
+        var a = 1;
+        a += 2;
+        print a;
+        a -= 1;
+        print a;
+        a *= 5;
+        print a;
+        a /= 4;
+        print a;
+        var s = "a";
+        s += "b";
+        print s;
+        "#,
+    );
+    assert_eq!(output.stdout, ["3", "2", "10", "2.5", "ab"]);
+}
+
+#[test]
+fn increments_return_the_old_or_new_value() {
+    let output = run(
+        "increments_return_the_old_or_new_value",
+        "
+        var b = 1;
+        print b++;
+        print b;
+        print ++b;
+        print b--;
+        print --b;
+        ",
+    );
+    assert_eq!(output.stdout, ["1", "2", "3", "3", "1"]);
+}
+
+#[test]
+fn fields_and_indexes_are_updated_once() {
+    let output = run(
+        "fields_and_indexes_are_updated_once",
+        "
+        var l = [1, 2];
+        var i = 0;
+        fun next() { i += 1; return i - 1; }
+        l[next()] += 10;
+        print l;
+        print i;
+        class P {}
+        var p = P();
+        p.x = 1;
+        p.x++;
+        print p.x;
+        ",
+    );
+    assert_eq!(output.stdout, ["[11, 2]", "1", "2"]);
+}
+
+#[test]
+fn invalid_targets_are_reported_at_the_operator() {
+    let output = run("invalid_compound_target", "1 += 2;");
+    assert_eq!(output.stderr, ["L1:3 Invalid assignment target"]);
+
+    let output = run(
+        "invalid_increment_target",
+        "var a = 1; var b = 2; (a + b)++;",
+    );
+    assert_eq!(output.stderr, ["L1:30 Invalid assignment target"]);
+}